@@ -1,6 +1,7 @@
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	opaque::SessionKeys, AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
+	MaxSignatories, Multisig, SecondTemplateModuleConfig, SessionConfig, Signature, SudoConfig,
+	SystemConfig, TemplateModuleConfig, Treasury, ValidatorSetConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -31,13 +32,63 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
-	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
+/// Generate a validator account together with its Aura and GRANDPA session keys.
+pub fn authority_keys_from_seed(s: &str) -> (AccountId, AuraId, GrandpaId) {
+	(
+		get_account_id_from_seed::<sr25519::Public>(s),
+		get_from_seed::<AuraId>(s),
+		get_from_seed::<GrandpaId>(s),
+	)
 }
 
-pub fn development_config() -> Result<ChainSpec, String> {
+fn session_keys(aura: AuraId, grandpa: GrandpaId) -> SessionKeys {
+	SessionKeys { aura, grandpa }
+}
+
+/// A `pallet_multisig` account to use as the sudo key instead of a single account.
+#[derive(Debug, Clone)]
+pub struct MultisigSudo {
+	/// The accounts that can approve sudo calls, in any order.
+	pub signatories: Vec<AccountId>,
+	/// The number of signatories that must approve a sudo call.
+	pub threshold: u16,
+}
+
+impl MultisigSudo {
+	/// Derive the multisig account, the same way `pallet_multisig` does when its signatories
+	/// dispatch a call.
+	pub fn account_id(&self) -> Result<AccountId, String> {
+		let mut signatories = self.signatories.clone();
+		signatories.sort();
+		signatories.dedup();
+
+		if signatories.len() < 2 || signatories.len() > MaxSignatories::get() as usize {
+			return Err(format!(
+				"A multisig sudo key needs between 2 and {} distinct signatories, got {}",
+				MaxSignatories::get(),
+				signatories.len(),
+			))
+		}
+		if self.threshold < 1 || self.threshold as usize > signatories.len() {
+			return Err(format!(
+				"A multisig sudo threshold must be between 1 and the {} signatories, got {}",
+				signatories.len(),
+				self.threshold,
+			))
+		}
+
+		Ok(Multisig::multi_account_id(&signatories, self.threshold))
+	}
+}
+
+/// The sudo key: the multisig account if one is given, otherwise `default`.
+fn sudo_key(multisig: Option<MultisigSudo>, default: AccountId) -> Result<AccountId, String> {
+	multisig.map_or(Ok(default), |multisig| multisig.account_id())
+}
+
+pub fn development_config(sudo_multisig: Option<MultisigSudo>) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+	let root_key = sudo_key(sudo_multisig, get_account_id_from_seed::<sr25519::Public>("Alice"))?;
 
 	Ok(ChainSpec::from_genesis(
 		// Name
@@ -51,7 +102,7 @@ pub fn development_config() -> Result<ChainSpec, String> {
 				// Initial PoA authorities
 				vec![authority_keys_from_seed("Alice")],
 				// Sudo account
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				root_key.clone(),
 				// Pre-funded accounts
 				vec![
 					get_account_id_from_seed::<sr25519::Public>("Alice"),
@@ -59,6 +110,12 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
+				// Initial template value
+				Some(42),
+				// Initial template entries
+				vec![(get_account_id_from_seed::<sr25519::Public>("Alice"), 1)],
+				// Second template instance authorities
+				vec![get_account_id_from_seed::<sr25519::Public>("Bob")],
 				true,
 			)
 		},
@@ -75,8 +132,9 @@ pub fn development_config() -> Result<ChainSpec, String> {
 	))
 }
 
-pub fn local_testnet_config() -> Result<ChainSpec, String> {
+pub fn local_testnet_config(sudo_multisig: Option<MultisigSudo>) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+	let root_key = sudo_key(sudo_multisig, get_account_id_from_seed::<sr25519::Public>("Alice"))?;
 
 	Ok(ChainSpec::from_genesis(
 		// Name
@@ -90,7 +148,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				// Initial PoA authorities
 				vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")],
 				// Sudo account
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				root_key.clone(),
 				// Pre-funded accounts
 				vec![
 					get_account_id_from_seed::<sr25519::Public>("Alice"),
@@ -106,6 +164,15 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				// Initial template value
+				Some(42),
+				// Initial template entries
+				vec![
+					(get_account_id_from_seed::<sr25519::Public>("Alice"), 1),
+					(get_account_id_from_seed::<sr25519::Public>("Bob"), 2),
+				],
+				// Second template instance authorities
+				vec![get_account_id_from_seed::<sr25519::Public>("Charlie")],
 				true,
 			)
 		},
@@ -125,9 +192,12 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
 	wasm_binary: &[u8],
-	initial_authorities: Vec<(AuraId, GrandpaId)>,
+	initial_authorities: Vec<(AccountId, AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	template_value: Option<u32>,
+	template_entries: Vec<(AccountId, u32)>,
+	second_template_authorities: Vec<AccountId>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -137,18 +207,46 @@ fn testnet_genesis(
 			changes_trie_config: Default::default(),
 		},
 		balances: BalancesConfig {
-			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
+			// Configure endowed accounts with initial balance of 1 << 60, and seed the treasury
+			// with the same amount.
+			balances: endowed_accounts
+				.iter()
+				.cloned()
+				.chain(std::iter::once(Treasury::account_id()))
+				.map(|k| (k, 1 << 60))
+				.collect(),
 		},
-		aura: AuraConfig {
-			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
+		validator_set: ValidatorSetConfig {
+			initial_validators: initial_authorities.iter().map(|x| x.0.clone()).collect(),
 		},
-		grandpa: GrandpaConfig {
-			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
+		session: SessionConfig {
+			keys: initial_authorities
+				.iter()
+				.map(|x| (x.0.clone(), x.0.clone(), session_keys(x.1.clone(), x.2.clone())))
+				.collect(),
 		},
+		// Aura and GRANDPA authorities are set by `Session` from the initial validators.
+		aura: AuraConfig { authorities: vec![] },
+		grandpa: GrandpaConfig { authorities: vec![] },
 		sudo: SudoConfig {
 			// Assign network admin rights.
 			key: root_key,
 		},
+		treasury: Default::default(),
+		template_module: TemplateModuleConfig {
+			something: template_value,
+			entries: template_entries,
+			// The offchain workers of the validators report the total of all entries.
+			authorities: initial_authorities.iter().map(|x| x.0.clone()).collect(),
+			phantom: Default::default(),
+		},
+		second_template_module: SecondTemplateModuleConfig {
+			something: None,
+			entries: Vec::new(),
+			// Signs with its own key, so that its transactions don't share nonces with the
+			// first instance's.
+			authorities: second_template_authorities,
+			phantom: Default::default(),
+		},
 	}
 }
@@ -1,3 +1,5 @@
+use crate::chain_spec::MultisigSudo;
+use node_template_runtime::AccountId;
 use sc_cli::RunCmd;
 use structopt::StructOpt;
 
@@ -8,6 +10,27 @@ pub struct Cli {
 
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	/// Make the sudo key of the dev or local chain a multisig account of these comma-separated
+	/// SS58 addresses. Requires `--sudo-threshold`.
+	#[structopt(long, use_delimiter = true)]
+	pub sudo_signatories: Vec<AccountId>,
+
+	/// The number of `--sudo-signatories` that must approve a sudo call.
+	#[structopt(long)]
+	pub sudo_threshold: Option<u16>,
+}
+
+impl Cli {
+	/// The multisig sudo key given by `--sudo-signatories` and `--sudo-threshold`, if any.
+	pub fn sudo_multisig(&self) -> Result<Option<MultisigSudo>, String> {
+		match (self.sudo_signatories.is_empty(), self.sudo_threshold) {
+			(true, None) => Ok(None),
+			(false, Some(threshold)) =>
+				Ok(Some(MultisigSudo { signatories: self.sudo_signatories.clone(), threshold })),
+			_ => Err("`--sudo-signatories` and `--sudo-threshold` must be given together".into()),
+		}
+	}
 }
 
 #[derive(Debug, StructOpt)]
@@ -50,9 +50,12 @@ impl SubstrateCli for Cli {
 	}
 
 	fn load_spec(&self, id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
+		let sudo_multisig = self.sudo_multisig()?;
 		Ok(match id {
-			"dev" => Box::new(chain_spec::development_config()?),
-			"" | "local" => Box::new(chain_spec::local_testnet_config()?),
+			"dev" => Box::new(chain_spec::development_config(sudo_multisig)?),
+			"" | "local" => Box::new(chain_spec::local_testnet_config(sudo_multisig)?),
+			_ if sudo_multisig.is_some() =>
+				return Err("`--sudo-signatories` only applies to the dev and local chains".into()),
 			path =>
 				Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?),
 		})
@@ -32,9 +32,13 @@ where
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_template_rpc::TemplateRuntimeApi<Block>,
+	C::Api: fee_multiplier_rpc::FeeMultiplierRuntimeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
+	use fee_multiplier_rpc::{FeeMultiplier, FeeMultiplierApi};
+	use pallet_template_rpc::{Template, TemplateApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
@@ -45,6 +49,10 @@ where
 
 	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone())));
 
+	io.extend_with(FeeMultiplierApi::to_delegate(FeeMultiplier::new(client.clone())));
+
+	io.extend_with(TemplateApi::to_delegate(Template::new(client.clone())));
+
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
 	// to call into the runtime.
@@ -6,10 +6,11 @@ use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
 pub use sc_executor::NativeElseWasmExecutor;
 use sc_finality_grandpa::SharedVoterState;
 use sc_keystore::LocalKeystore;
-use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
+use sc_service::{error::Error as ServiceError, ChainType, Configuration, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sp_consensus::SlotData;
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
+use sp_keystore::SyncCryptoStore;
 use std::{sync::Arc, time::Duration};
 
 // Our native executor instance.
@@ -194,6 +195,25 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		})?;
 
 	if config.offchain_worker.enabled {
+		// Give the offchain workers of both template pallet instances a key to sign with on
+		// development chains. On any other chain the keys have to be inserted with the
+		// `author_insertKey` RPC.
+		if config.chain_spec.chain_type() == ChainType::Development {
+			for (key_type, seed) in [
+				(node_template_runtime::pallet_template::KEY_TYPE, "//Alice"),
+				(node_template_runtime::pallet_template::SECOND_KEY_TYPE, "//Bob"),
+			] {
+				SyncCryptoStore::sr25519_generate_new(
+					&*keystore_container.sync_keystore(),
+					key_type,
+					Some(seed),
+				)
+				.map_err(|e| {
+					ServiceError::Other(format!("Failed to insert offchain key: {}", e))
+				})?;
+			}
+		}
+
 		sc_service::build_offchain_workers(
 			&config,
 			task_manager.spawn_handle(),
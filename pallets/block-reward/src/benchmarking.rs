@@ -0,0 +1,38 @@
+//! Benchmarking setup for pallet-block-reward
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as BlockReward;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::{Currency, EnsureOrigin, Get};
+use sp_runtime::{traits::Zero, Perbill};
+
+benchmarks! {
+	set_schedule {
+		let origin = T::AdminOrigin::successful_origin();
+		let reward = T::InitialRewardPerBlock::get();
+	}: _<T::Origin>(origin, reward, Perbill::from_percent(10))
+	verify {
+		assert_eq!(BlockReward::<T>::schedule().max_inflation, Perbill::from_percent(10));
+	}
+
+	// Reward a new author at the start of a new period, so that the budget is recomputed and the
+	// author's account is created.
+	reward_author {
+		let author: T::AccountId = account("author", 0, 0);
+		let whale: T::AccountId = account("whale", 0, 0);
+		T::Currency::make_free_balance_be(&whale, T::Currency::minimum_balance() * 1_000u32.into());
+		Schedule::<T>::put(InflationSchedule {
+			reward_per_block: T::Currency::minimum_balance() * 10u32.into(),
+			max_inflation: Perbill::one(),
+		});
+	}: {
+		BlockReward::<T>::reward_author(author.clone());
+	}
+	verify {
+		assert!(!T::Currency::free_balance(&author).is_zero());
+	}
+}
+
+impl_benchmark_test_suite!(BlockReward, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,191 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Block Reward Pallet
+//!
+//! Mints a reward to the author of every block, as reported by `pallet_authorship` through its
+//! `EventHandler`.
+//!
+//! The reward and the inflation cap are kept on chain in an [`InflationSchedule`] that
+//! `AdminOrigin` can change. Block rewards never add more than `max_inflation` of the total
+//! issuance at the start of an `InflationPeriod` during that period. Once the budget of a period
+//! is used up, authors go unrewarded until the next period starts.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+use codec::{Decode, Encode};
+use sp_runtime::{Perbill, RuntimeDebug};
+pub use weights::WeightInfo;
+
+/// The block reward and the cap on the issuance it adds.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct InflationSchedule<Balance> {
+	/// The amount minted to the author of every block.
+	pub reward_per_block: Balance,
+	/// The most block rewards can add to the total issuance during one `InflationPeriod`, as a
+	/// share of the total issuance at the start of the period.
+	pub max_inflation: Perbill,
+}
+
+/// What is left to mint in the current `InflationPeriod`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct PeriodBudget<BlockNumber, Balance> {
+	/// The index of the period, counted in `InflationPeriod`s since genesis.
+	pub period: BlockNumber,
+	/// The amount that can still be minted in the period.
+	pub remaining: Balance,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::{InflationSchedule, PeriodBudget, WeightInfo};
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, Imbalance},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::{
+		traits::{Saturating, Zero},
+		Perbill,
+	};
+
+	/// The balance type of the currency in which rewards are minted.
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The currency in which rewards are minted.
+		type Currency: Currency<Self::AccountId>;
+		/// The origin that can change the inflation schedule.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+		/// The number of blocks over which `max_inflation` applies.
+		#[pallet::constant]
+		type InflationPeriod: Get<Self::BlockNumber>;
+		/// The reward per block until `AdminOrigin` sets a schedule.
+		#[pallet::constant]
+		type InitialRewardPerBlock: Get<BalanceOf<Self>>;
+		/// The inflation cap until `AdminOrigin` sets a schedule.
+		#[pallet::constant]
+		type InitialMaxInflation: Get<Perbill>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::type_value]
+	pub fn DefaultSchedule<T: Config>() -> InflationSchedule<BalanceOf<T>> {
+		InflationSchedule {
+			reward_per_block: T::InitialRewardPerBlock::get(),
+			max_inflation: T::InitialMaxInflation::get(),
+		}
+	}
+
+	/// The current inflation schedule.
+	#[pallet::storage]
+	#[pallet::getter(fn schedule)]
+	pub type Schedule<T: Config> =
+		StorageValue<_, InflationSchedule<BalanceOf<T>>, ValueQuery, DefaultSchedule<T>>;
+
+	/// What is left to mint in the current period. Replaced when the first block of a new period
+	/// is rewarded.
+	#[pallet::storage]
+	#[pallet::getter(fn budget)]
+	pub type Budget<T: Config> = StorageValue<_, PeriodBudget<T::BlockNumber, BalanceOf<T>>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T> = "Balance")]
+	pub enum Event<T: Config> {
+		/// The author of the current block was rewarded.
+		AuthorRewarded { author: T::AccountId, amount: BalanceOf<T> },
+		/// A new inflation schedule was set.
+		ScheduleSet { reward_per_block: BalanceOf<T>, max_inflation: Perbill },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// The reward itself is minted when `pallet_authorship` reports the author, which doesn't
+		/// account for any weight, so it is accounted for here.
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			T::WeightInfo::reward_author()
+		}
+
+		fn integrity_test() {
+			assert!(!T::InflationPeriod::get().is_zero(), "InflationPeriod must not be zero");
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the inflation schedule.
+		///
+		/// The new reward applies from the next block. The new `max_inflation` applies from the
+		/// next `InflationPeriod`.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[pallet::weight(T::WeightInfo::set_schedule())]
+		pub fn set_schedule(
+			origin: OriginFor<T>,
+			reward_per_block: BalanceOf<T>,
+			max_inflation: Perbill,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			<Schedule<T>>::put(InflationSchedule { reward_per_block, max_inflation });
+
+			Self::deposit_event(Event::ScheduleSet { reward_per_block, max_inflation });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Mint the block reward to `author`, within the budget of the current period.
+		pub(crate) fn reward_author(author: T::AccountId) {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let period = now / T::InflationPeriod::get();
+			let schedule = Self::schedule();
+
+			let remaining = match Self::budget() {
+				Some(budget) if budget.period == period => budget.remaining,
+				_ => schedule.max_inflation * T::Currency::total_issuance(),
+			};
+			let amount = schedule.reward_per_block.min(remaining);
+			let minted = if amount.is_zero() {
+				Zero::zero()
+			} else {
+				// Dropping the imbalance raises the total issuance by what was actually minted,
+				// which is nothing if `amount` can't create the author's account.
+				T::Currency::deposit_creating(&author, amount).peek()
+			};
+			<Budget<T>>::put(PeriodBudget { period, remaining: remaining.saturating_sub(minted) });
+
+			if !minted.is_zero() {
+				Self::deposit_event(Event::AuthorRewarded { author, amount: minted });
+			}
+		}
+	}
+}
+
+impl<T: Config> pallet_authorship::EventHandler<T::AccountId, T::BlockNumber> for Pallet<T> {
+	fn note_author(author: T::AccountId) {
+		Self::reward_author(author)
+	}
+
+	fn note_uncle(_author: T::AccountId, _age: T::BlockNumber) {}
+}
@@ -0,0 +1,128 @@
+use crate::{mock::*, Event as BlockRewardEvent, InflationSchedule, PeriodBudget};
+use frame_support::{assert_noop, assert_ok, traits::Hooks, weights::Weight};
+use pallet_authorship::EventHandler;
+use sp_runtime::{DispatchError, Perbill};
+
+use crate::weights::WeightInfo;
+
+fn note_author(author: u64) {
+	<BlockReward as EventHandler<u64, u64>>::note_author(author);
+}
+
+#[test]
+fn author_is_rewarded() {
+	new_test_ext().execute_with(|| {
+		note_author(1);
+
+		assert_eq!(Balances::free_balance(1), 110);
+		assert_eq!(Balances::total_issuance(), 310);
+		// 10% of the 300 issued at the start of the period, less this block's reward.
+		assert_eq!(BlockReward::budget(), Some(PeriodBudget { period: 0, remaining: 20 }));
+		System::assert_last_event(Event::BlockReward(BlockRewardEvent::AuthorRewarded {
+			author: 1,
+			amount: 10,
+		}));
+	});
+}
+
+#[test]
+fn new_accounts_can_be_rewarded() {
+	new_test_ext().execute_with(|| {
+		note_author(4);
+
+		assert_eq!(Balances::free_balance(4), 10);
+	});
+}
+
+#[test]
+fn rewards_stop_once_the_budget_is_used_up() {
+	new_test_ext().execute_with(|| {
+		for block in 1..=3 {
+			System::set_block_number(block);
+			note_author(1);
+		}
+		assert_eq!(Balances::free_balance(1), 130);
+
+		// The budget of 30 is used up and the growth of the issuance is not taken into account
+		// until the next period.
+		System::set_block_number(4);
+		System::reset_events();
+		note_author(2);
+		assert_eq!(Balances::free_balance(2), 100);
+		assert_eq!(Balances::total_issuance(), 330);
+		assert!(System::events().is_empty());
+	});
+}
+
+#[test]
+fn last_reward_of_a_period_is_capped() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BlockReward::set_schedule(Origin::root(), 25, Perbill::from_percent(10)));
+
+		note_author(1);
+		note_author(2);
+
+		assert_eq!(Balances::free_balance(1), 125);
+		assert_eq!(Balances::free_balance(2), 105);
+		assert_eq!(BlockReward::budget(), Some(PeriodBudget { period: 0, remaining: 0 }));
+	});
+}
+
+#[test]
+fn budget_is_renewed_every_period() {
+	new_test_ext().execute_with(|| {
+		for block in 1..=4 {
+			System::set_block_number(block);
+			note_author(1);
+		}
+		assert_eq!(Balances::total_issuance(), 330);
+
+		System::set_block_number(10);
+		note_author(1);
+
+		assert_eq!(Balances::free_balance(1), 140);
+		// 10% of the 330 issued at the start of the new period, less this block's reward.
+		assert_eq!(BlockReward::budget(), Some(PeriodBudget { period: 1, remaining: 23 }));
+	});
+}
+
+#[test]
+fn only_admin_can_set_schedule() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			BlockReward::set_schedule(Origin::signed(1), 20, Perbill::from_percent(20)),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_schedule_applies_to_later_rewards() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			BlockReward::schedule(),
+			InflationSchedule { reward_per_block: 10, max_inflation: Perbill::from_percent(10) }
+		);
+		note_author(1);
+
+		assert_ok!(BlockReward::set_schedule(Origin::root(), 5, Perbill::from_percent(50)));
+		System::assert_last_event(Event::BlockReward(BlockRewardEvent::ScheduleSet {
+			reward_per_block: 5,
+			max_inflation: Perbill::from_percent(50),
+		}));
+
+		System::set_block_number(2);
+		note_author(1);
+		assert_eq!(Balances::free_balance(1), 115);
+		// The new cap only takes effect in the next period.
+		assert_eq!(BlockReward::budget(), Some(PeriodBudget { period: 0, remaining: 15 }));
+	});
+}
+
+#[test]
+fn reward_weight_is_accounted_for_on_initialize() {
+	new_test_ext().execute_with(|| {
+		let weight: Weight = BlockReward::on_initialize(1);
+		assert_eq!(weight, <() as WeightInfo>::reward_author());
+	});
+}
@@ -0,0 +1,59 @@
+//! Weights for pallet_block_reward
+//!
+//! These weights are estimated by hand from the storage accesses of each call and have not
+//! been generated by the benchmark CLI. Regenerate them on reference hardware with the
+//! command below, which overwrites this file, before relying on them in production.
+
+// Command to generate the weights:
+// ./target/release/node-template
+// benchmark
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet=pallet_block_reward
+// --extrinsic=*
+// --steps=50
+// --repeat=20
+// --output=./pallets/block-reward/src/weights.rs
+// --template=./.maintain/frame-weight-template.hbs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_block_reward.
+pub trait WeightInfo {
+	fn set_schedule() -> Weight;
+	fn reward_author() -> Weight;
+}
+
+/// Hand-estimated weights for pallet_block_reward, see the module documentation.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn set_schedule() -> Weight {
+		(14_208_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn reward_author() -> Weight {
+		(46_735_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn set_schedule() -> Weight {
+		(14_208_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn reward_author() -> Weight {
+		(46_735_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+}
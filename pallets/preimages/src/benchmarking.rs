@@ -0,0 +1,67 @@
+//! Benchmarking setup for pallet-preimages
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Preimages;
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_support::{
+	traits::{Currency, EnsureOrigin, Get},
+	weights::Weight,
+};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Hash};
+use sp_std::prelude::*;
+
+/// Create an account that can afford any preimage deposit.
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, 0);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+	who
+}
+
+/// Note a `remark` call whose encoding is `s` bytes plus a few bytes of call index and length
+/// prefix, and return its hash.
+fn note_remark<T: Config>(provider: T::AccountId, s: u32) -> T::Hash {
+	let call: <T as Config>::Call = frame_system::Call::<T>::remark(vec![1u8; s as usize]).into();
+	let bytes = call.encode();
+	let hash = T::Hashing::hash(&bytes);
+	Preimages::<T>::note_preimage(RawOrigin::Signed(provider).into(), bytes)
+		.expect("the preimage fits and the provider is funded; qed");
+	hash
+}
+
+benchmarks! {
+	note_preimage {
+		let s in 0 .. T::MaxPreimageLen::get();
+		let caller = funded_account::<T>("caller", 0);
+		let bytes = vec![1u8; s as usize];
+		let hash = T::Hashing::hash(&bytes);
+	}: _(RawOrigin::Signed(caller), bytes)
+	verify {
+		assert!(Preimages::<T>::preimage(hash).is_some());
+	}
+
+	remove_preimage {
+		// Leave room for the call index and length prefix of the remark.
+		let s in 0 .. T::MaxPreimageLen::get() - 16;
+		let caller = funded_account::<T>("caller", 0);
+		let hash = note_remark::<T>(caller.clone(), s);
+	}: _(RawOrigin::Signed(caller), hash)
+	verify {
+		assert!(Preimages::<T>::preimage(hash).is_none());
+	}
+
+	// Dispatch a `remark`, which does no work of its own beyond being decoded.
+	dispatch_preimage {
+		let s in 0 .. T::MaxPreimageLen::get() - 16;
+		let hash = note_remark::<T>(funded_account::<T>("provider", 0), s);
+		let origin = T::DispatchOrigin::successful_origin();
+	}: _<T::Origin>(origin, hash, Weight::max_value())
+	verify {
+		assert!(Preimages::<T>::preimage(hash).is_none());
+	}
+}
+
+impl_benchmark_test_suite!(Preimages, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,226 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Preimages Pallet
+//!
+//! Stores encoded calls under their hash, so that a large call such as `System::set_code` is
+//! uploaded once and can then be scheduled or approved by its hash alone.
+//!
+//! Any account can note a preimage against a deposit of `PreimageBaseDeposit` plus
+//! `PreimageByteDeposit` for each byte, and remove it again to get the deposit back.
+//! `DispatchOrigin` can dispatch a noted call with the root origin, e.g. from a call scheduled
+//! through `pallet_scheduler`. The preimage is removed and its deposit returned to the account
+//! that noted it when it is dispatched.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+pub use weights::WeightInfo;
+
+/// A noted preimage together with the account that noted it and the deposit held for it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Preimage<AccountId, Balance, Data> {
+	/// The account that noted the preimage.
+	pub provider: AccountId,
+	/// The amount reserved from `provider` for storing the preimage.
+	pub deposit: Balance,
+	/// The encoded call.
+	pub data: Data,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::{Preimage, WeightInfo};
+	use codec::Decode;
+	use frame_support::{
+		dispatch::{DispatchResult, Dispatchable, GetDispatchInfo, PostDispatchInfo},
+		pallet_prelude::*,
+		traits::{Currency, ReservableCurrency},
+		weights::extract_actual_weight,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Hash, Saturating};
+	use sp_std::{convert::TryFrom, prelude::*};
+
+	/// The balance type of the currency in which deposits are held.
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The call type that preimages are decoded into.
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>;
+		/// The currency in which deposits are held.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The origin that can dispatch a noted call as root.
+		type DispatchOrigin: EnsureOrigin<Self::Origin>;
+		/// The longest preimage that can be noted, in bytes.
+		#[pallet::constant]
+		type MaxPreimageLen: Get<u32>;
+		/// The deposit for noting a preimage, on top of `PreimageByteDeposit`.
+		#[pallet::constant]
+		type PreimageBaseDeposit: Get<BalanceOf<Self>>;
+		/// The deposit for each byte of a noted preimage.
+		#[pallet::constant]
+		type PreimageByteDeposit: Get<BalanceOf<Self>>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Noted preimages, keyed by the hash of their data.
+	#[pallet::storage]
+	#[pallet::getter(fn preimage)]
+	pub type Preimages<T: Config> = StorageMap<
+		_,
+		Identity,
+		T::Hash,
+		Preimage<T::AccountId, BalanceOf<T>, BoundedVec<u8, T::MaxPreimageLen>>,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(T::Hash = "Hash", T::AccountId = "AccountId", BalanceOf<T> = "Balance")]
+	pub enum Event<T: Config> {
+		/// A preimage was noted and `deposit` reserved from `provider`.
+		PreimageNoted { hash: T::Hash, provider: T::AccountId, deposit: BalanceOf<T> },
+		/// A preimage was removed by the account that noted it.
+		PreimageRemoved { hash: T::Hash },
+		/// A noted call was dispatched as root and its preimage removed.
+		PreimageDispatched { hash: T::Hash, result: DispatchResult },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The preimage is longer than `MaxPreimageLen`.
+		TooLarge,
+		/// The preimage has already been noted.
+		AlreadyNoted,
+		/// There is no preimage with the given hash.
+		NotNoted,
+		/// The preimage was noted by another account.
+		NotProvider,
+		/// The preimage doesn't decode into a call.
+		Undecodable,
+		/// The weight of the call is above the given bound.
+		WeightBoundTooLow,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Note `bytes` as a preimage under its hash.
+		///
+		/// `PreimageBaseDeposit` plus `PreimageByteDeposit` for each byte is reserved from the
+		/// signer until the preimage is removed or dispatched.
+		#[pallet::weight(T::WeightInfo::note_preimage(bytes.len() as u32))]
+		pub fn note_preimage(origin: OriginFor<T>, bytes: Vec<u8>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let data = BoundedVec::<u8, T::MaxPreimageLen>::try_from(bytes)
+				.map_err(|_| Error::<T>::TooLarge)?;
+			let hash = T::Hashing::hash(&data);
+			ensure!(!<Preimages<T>>::contains_key(&hash), Error::<T>::AlreadyNoted);
+
+			let deposit = T::PreimageByteDeposit::get()
+				.saturating_mul((data.len() as u32).into())
+				.saturating_add(T::PreimageBaseDeposit::get());
+			T::Currency::reserve(&who, deposit)?;
+			<Preimages<T>>::insert(&hash, Preimage { provider: who.clone(), deposit, data });
+
+			Self::deposit_event(Event::PreimageNoted { hash, provider: who, deposit });
+			Ok(().into())
+		}
+
+		/// Remove a preimage noted by the signer and refund its deposit.
+		///
+		/// The length of the preimage is only known once it is read, so the weight assumes a
+		/// preimage of `MaxPreimageLen` bytes and the difference is refunded afterwards.
+		#[pallet::weight(T::WeightInfo::remove_preimage(T::MaxPreimageLen::get()))]
+		pub fn remove_preimage(origin: OriginFor<T>, hash: T::Hash) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let preimage = <Preimages<T>>::get(&hash).ok_or(Error::<T>::NotNoted)?;
+			ensure!(preimage.provider == who, Error::<T>::NotProvider);
+			<Preimages<T>>::remove(&hash);
+			T::Currency::unreserve(&who, preimage.deposit);
+
+			Self::deposit_event(Event::PreimageRemoved { hash });
+			Ok(Some(T::WeightInfo::remove_preimage(preimage.data.len() as u32)).into())
+		}
+
+		/// Dispatch the call noted under `hash` with the root origin.
+		///
+		/// `weight_bound` must be at least the weight of the call, which is only known once the
+		/// preimage is decoded. This call takes on the dispatch class of the noted call, so that
+		/// an operational call such as `System::set_code` can use the block space reserved for
+		/// operational calls. The preimage is removed and its deposit refunded whether or not the
+		/// call succeeds; its result is reported in the `PreimageDispatched` event.
+		///
+		/// The dispatch origin for this call must be `DispatchOrigin`.
+		#[pallet::weight((
+			T::WeightInfo::dispatch_preimage(T::MaxPreimageLen::get())
+				.saturating_add(*weight_bound),
+			Pallet::<T>::preimage_class(hash),
+		))]
+		pub fn dispatch_preimage(
+			origin: OriginFor<T>,
+			hash: T::Hash,
+			weight_bound: Weight,
+		) -> DispatchResultWithPostInfo {
+			T::DispatchOrigin::ensure_origin(origin)?;
+
+			let preimage = <Preimages<T>>::get(&hash).ok_or(Error::<T>::NotNoted)?;
+			let call = <T as Config>::Call::decode(&mut &preimage.data[..])
+				.map_err(|_| Error::<T>::Undecodable)?;
+			let info = call.get_dispatch_info();
+			ensure!(info.weight <= weight_bound, Error::<T>::WeightBoundTooLow);
+
+			<Preimages<T>>::remove(&hash);
+			T::Currency::unreserve(&preimage.provider, preimage.deposit);
+
+			let result = call.dispatch(frame_system::RawOrigin::Root.into());
+			let weight = T::WeightInfo::dispatch_preimage(preimage.data.len() as u32)
+				.saturating_add(extract_actual_weight(&result, &info));
+
+			Self::deposit_event(Event::PreimageDispatched {
+				hash,
+				result: result.map(|_| ()).map_err(|e| e.error),
+			});
+			Ok(Some(weight).into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The dispatch class of the call noted under `hash`.
+		///
+		/// Falls back to `Normal` if nothing decodable is noted under `hash`, in which case
+		/// `dispatch_preimage` fails anyway.
+		pub fn preimage_class(hash: &T::Hash) -> DispatchClass {
+			<Preimages<T>>::get(hash)
+				.and_then(|preimage| <T as Config>::Call::decode(&mut &preimage.data[..]).ok())
+				.map_or(DispatchClass::Normal, |call| call.get_dispatch_info().class)
+		}
+	}
+}
@@ -0,0 +1,165 @@
+use crate::{mock::*, Error, Event as PreimagesEvent};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	weights::{DispatchClass, GetDispatchInfo},
+};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	DispatchError, Perbill,
+};
+
+/// A root-only call that gives account 4 a balance of 50.
+fn set_balance_call() -> Call {
+	Call::Balances(pallet_balances::Call::set_balance(4, 50, 0))
+}
+
+/// Note `call` from account 1 and return its hash.
+fn note(call: &Call) -> sp_core::H256 {
+	assert_ok!(Preimages::note_preimage(Origin::signed(1), call.encode()));
+	BlakeTwo256::hash(&call.encode())
+}
+
+#[test]
+fn note_preimage_reserves_deposit() {
+	new_test_ext().execute_with(|| {
+		let bytes = set_balance_call().encode();
+		let hash = note(&set_balance_call());
+
+		let deposit = 10 + bytes.len() as u64;
+		assert_eq!(Balances::reserved_balance(1), deposit);
+		assert_eq!(Preimages::preimage(hash).unwrap().data.into_inner(), bytes);
+		System::assert_last_event(Event::Preimages(PreimagesEvent::PreimageNoted {
+			hash,
+			provider: 1,
+			deposit,
+		}));
+	});
+}
+
+#[test]
+fn note_preimage_rejects_duplicate_and_large_preimages() {
+	new_test_ext().execute_with(|| {
+		note(&set_balance_call());
+		assert_noop!(
+			Preimages::note_preimage(Origin::signed(2), set_balance_call().encode()),
+			Error::<Test>::AlreadyNoted
+		);
+		assert_noop!(
+			Preimages::note_preimage(Origin::signed(2), vec![0; 65]),
+			Error::<Test>::TooLarge
+		);
+	});
+}
+
+#[test]
+fn only_provider_can_remove_preimage() {
+	new_test_ext().execute_with(|| {
+		let hash = note(&set_balance_call());
+
+		assert_noop!(
+			Preimages::remove_preimage(Origin::signed(2), hash),
+			Error::<Test>::NotProvider
+		);
+		assert_ok!(Preimages::remove_preimage(Origin::signed(1), hash));
+
+		assert!(Preimages::preimage(hash).is_none());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_noop!(
+			Preimages::remove_preimage(Origin::signed(1), hash),
+			Error::<Test>::NotNoted
+		);
+	});
+}
+
+#[test]
+fn only_dispatch_origin_can_dispatch_preimage() {
+	new_test_ext().execute_with(|| {
+		let hash = note(&set_balance_call());
+
+		assert_noop!(
+			Preimages::dispatch_preimage(Origin::signed(1), hash, u64::MAX),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn dispatch_preimage_dispatches_as_root_and_refunds_deposit() {
+	new_test_ext().execute_with(|| {
+		let call = set_balance_call();
+		let hash = note(&call);
+
+		assert_ok!(Preimages::dispatch_preimage(
+			Origin::root(),
+			hash,
+			call.get_dispatch_info().weight
+		));
+
+		assert_eq!(Balances::free_balance(4), 50);
+		assert!(Preimages::preimage(hash).is_none());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		System::assert_last_event(Event::Preimages(PreimagesEvent::PreimageDispatched {
+			hash,
+			result: Ok(()),
+		}));
+	});
+}
+
+#[test]
+fn failed_call_still_uses_up_preimage() {
+	new_test_ext().execute_with(|| {
+		// Account 4 has nothing to transfer.
+		let call = Call::Balances(pallet_balances::Call::force_transfer(4, 1, 10));
+		let hash = note(&call);
+
+		assert_ok!(Preimages::dispatch_preimage(Origin::root(), hash, u64::MAX));
+
+		assert!(Preimages::preimage(hash).is_none());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(matches!(
+			System::events().last().unwrap().event,
+			Event::Preimages(PreimagesEvent::PreimageDispatched { result: Err(_), .. })
+		));
+	});
+}
+
+#[test]
+fn dispatch_preimage_checks_weight_bound_and_encoding() {
+	new_test_ext().execute_with(|| {
+		let call = set_balance_call();
+		let hash = note(&call);
+		assert_noop!(
+			Preimages::dispatch_preimage(Origin::root(), hash, call.get_dispatch_info().weight - 1),
+			Error::<Test>::WeightBoundTooLow
+		);
+
+		let bytes = vec![0xff; 4];
+		let hash = BlakeTwo256::hash(&bytes);
+		assert_ok!(Preimages::note_preimage(Origin::signed(1), bytes));
+		assert_noop!(
+			Preimages::dispatch_preimage(Origin::root(), hash, u64::MAX),
+			Error::<Test>::Undecodable
+		);
+	});
+}
+
+#[test]
+fn dispatch_preimage_takes_on_the_class_of_the_call() {
+	new_test_ext().execute_with(|| {
+		let dispatch = |hash, weight| Call::Preimages(crate::Call::dispatch_preimage(hash, weight));
+
+		let call = Call::System(frame_system::Call::fill_block(Perbill::from_percent(1)));
+		let info = call.get_dispatch_info();
+		assert_eq!(info.class, DispatchClass::Operational);
+		let hash = note(&call);
+		let class = dispatch(hash, info.weight).get_dispatch_info().class;
+		assert_eq!(class, DispatchClass::Operational);
+		assert_ok!(Preimages::dispatch_preimage(Origin::root(), hash, info.weight));
+
+		// Nothing is noted under the hash anymore.
+		assert_eq!(dispatch(hash, info.weight).get_dispatch_info().class, DispatchClass::Normal);
+		let hash = note(&set_balance_call());
+		assert_eq!(dispatch(hash, u64::MAX).get_dispatch_info().class, DispatchClass::Normal);
+	});
+}
@@ -0,0 +1,78 @@
+//! Weights for pallet_preimages
+//!
+//! These weights are estimated by hand from the storage accesses of each call and have not
+//! been generated by the benchmark CLI. Regenerate them on reference hardware with the
+//! command below, which overwrites this file, before relying on them in production.
+
+// Command to generate the weights:
+// ./target/release/node-template
+// benchmark
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet=pallet_preimages
+// --extrinsic=*
+// --steps=50
+// --repeat=20
+// --output=./pallets/preimages/src/weights.rs
+// --template=./.maintain/frame-weight-template.hbs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_preimages.
+pub trait WeightInfo {
+	fn note_preimage(s: u32, ) -> Weight;
+	fn remove_preimage(s: u32, ) -> Weight;
+	fn dispatch_preimage(s: u32, ) -> Weight;
+}
+
+/// Hand-estimated weights for pallet_preimages, see the module documentation.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn note_preimage(s: u32, ) -> Weight {
+		(0 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn remove_preimage(s: u32, ) -> Weight {
+		(41_207_000 as Weight)
+			.saturating_add((1_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn dispatch_preimage(s: u32, ) -> Weight {
+		(45_386_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn note_preimage(s: u32, ) -> Weight {
+		(0 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn remove_preimage(s: u32, ) -> Weight {
+		(41_207_000 as Weight)
+			.saturating_add((1_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn dispatch_preimage(s: u32, ) -> Weight {
+		(45_386_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
@@ -0,0 +1,11 @@
+//! Runtime API definition for the template pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+sp_api::decl_runtime_apis! {
+	/// The API to query the template pallet's storage.
+	pub trait TemplateApi {
+		/// The current global value, if one has been stored.
+		fn get_value() -> Option<u32>;
+	}
+}
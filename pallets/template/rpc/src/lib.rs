@@ -0,0 +1,65 @@
+//! RPC interface for the template pallet.
+
+use std::sync::Arc;
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+pub use pallet_template_runtime_api::TemplateApi as TemplateRuntimeApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// Template RPC methods.
+#[rpc]
+pub trait TemplateApi<BlockHash> {
+	/// Get the global value stored by the template pallet, if any.
+	#[rpc(name = "template_getValue")]
+	fn get_value(&self, at: Option<BlockHash>) -> Result<Option<u32>>;
+}
+
+/// A struct that implements the [`TemplateApi`].
+pub struct Template<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Template<C, B> {
+	/// Create new `Template` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block> TemplateApi<<Block as BlockT>::Hash> for Template<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: TemplateRuntimeApi<Block>,
+{
+	fn get_value(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Option<u32>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_value(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query the template value.".into(),
+			data: Some(e.to_string().into()),
+		})
+	}
+}
@@ -4,16 +4,232 @@ use super::*;
 
 #[allow(unused)]
 use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
-use frame_system::RawOrigin;
+use frame_benchmarking::{
+	account, benchmarks_instance_pallet, impl_benchmark_test_suite, whitelisted_caller,
+};
+use codec::Encode;
+use frame_support::traits::{Currency, EnsureOrigin, Get, Hooks};
+use frame_system::{offchain::AppCrypto, RawOrigin};
+use sp_runtime::{
+	traits::{BlakeTwo256, Bounded, Hash, IdentifyAccount, StaticLookup, Zero},
+	DispatchError, RuntimeAppPublic,
+};
+use sp_std::prelude::*;
 
-benchmarks! {
+fn assert_last_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::Event) {
+	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+/// Create an account that can afford any storage deposit.
+fn funded_account<T: Config<I>, I: 'static>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, 0);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T, I>::max_value() / 2u32.into());
+	who
+}
+
+benchmarks_instance_pallet! {
 	do_something {
-		let s in 0 .. 100;
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+	}: _(RawOrigin::Signed(caller), 42)
+	verify {
+		assert_eq!(Template::<T, I>::something(), Some(42));
+	}
+
+	cause_error {
+		let caller: T::AccountId = whitelisted_caller();
+		Something::<T, I>::put(StoredValue { value: 42, updated_at: Default::default() });
+	}: _(RawOrigin::Signed(caller))
+	verify {
+		assert_eq!(Template::<T, I>::something(), Some(43));
+	}
+
+	cause_error_none_value {
+		let caller: T::AccountId = whitelisted_caller();
+	}: {
+		assert!(Template::<T, I>::cause_error(RawOrigin::Signed(caller).into()).is_err());
+	}
+	verify {
+		assert_eq!(Template::<T, I>::something(), None);
+	}
+
+	set_entry {
+		let caller = funded_account::<T, I>("caller", 0);
+	}: _(RawOrigin::Signed(caller.clone()), 42)
+	verify {
+		assert_eq!(Entries::<T, I>::get(&caller), Some(42));
+		assert_eq!(Deposits::<T, I>::get(&caller), Some(T::StorageDeposit::get()));
+	}
+
+	increment_entry {
+		let caller = funded_account::<T, I>("caller", 0);
+		Template::<T, I>::set_entry(RawOrigin::Signed(caller.clone()).into(), 42)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Entries::<T, I>::get(&caller), Some(43));
+	}
+
+	clear_entry {
+		let caller = funded_account::<T, I>("caller", 0);
+		Template::<T, I>::set_entry(RawOrigin::Signed(caller.clone()).into(), 42)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Entries::<T, I>::get(&caller), None);
+		assert_eq!(Deposits::<T, I>::get(&caller), None);
+	}
+
+	transfer_entry {
+		let caller = funded_account::<T, I>("caller", 0);
+		let new_owner = funded_account::<T, I>("new_owner", 0);
+		let new_owner_lookup = T::Lookup::unlookup(new_owner.clone());
+		Template::<T, I>::set_entry(RawOrigin::Signed(caller.clone()).into(), 42)?;
+	}: _(RawOrigin::Signed(caller.clone()), new_owner_lookup)
+	verify {
+		assert_eq!(Entries::<T, I>::get(&caller), None);
+		assert_eq!(Entries::<T, I>::get(&new_owner), Some(42));
+		assert_eq!(Deposits::<T, I>::get(&new_owner), Some(T::StorageDeposit::get()));
+	}
+
+	force_set {
+		let who: T::AccountId = account("who", 0, 0);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, who_lookup, 42)
+	verify {
+		assert_eq!(Entries::<T, I>::get(&who), Some(42));
+	}
+
+	force_clear {
+		let who = funded_account::<T, I>("who", 0);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+		Template::<T, I>::set_entry(RawOrigin::Signed(who.clone()).into(), 42)?;
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, who_lookup)
+	verify {
+		assert_eq!(Entries::<T, I>::get(&who), None);
+		assert_eq!(Deposits::<T, I>::get(&who), None);
+	}
+
+	kill_all {
+		let n in 1 .. 1_000;
+		for i in 0 .. n {
+			let who = funded_account::<T, I>("who", i);
+			Template::<T, I>::set_entry(RawOrigin::Signed(who).into(), i)?;
+		}
+		Something::<T, I>::put(StoredValue { value: 42, updated_at: Default::default() });
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, n)
+	verify {
+		assert_eq!(Template::<T, I>::something(), None);
+		assert_eq!(Entries::<T, I>::iter().count(), 0);
+	}
+
+	queue_entry_update {
+		let caller = funded_account::<T, I>("caller", 0);
+		Template::<T, I>::set_entry(RawOrigin::Signed(caller.clone()).into(), 42)?;
+		// Leave room for exactly one more update, so the whole queue is decoded, searched for the
+		// caller and encoded again.
+		for i in 1 .. T::MaxQueueLen::get() {
+			PendingUpdates::<T, I>::try_append((account::<T::AccountId>("queued", i, 0), i))
+				.map_err(|_| "queue is full")?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()), 43)
+	verify {
+		assert_eq!(PendingUpdates::<T, I>::get().last(), Some(&(caller, 43)));
+	}
+
+	process_queue {
+		let n in 0 .. T::MaxQueueLen::get();
+		for i in 0 .. n {
+			let who = funded_account::<T, I>("who", i);
+			Template::<T, I>::set_entry(RawOrigin::Signed(who.clone()).into(), 0)?;
+			PendingUpdates::<T, I>::try_append((who, i + 1)).map_err(|_| "queue is full")?;
+		}
+	}: {
+		Template::<T, I>::on_initialize(T::ProcessingPeriod::get());
+	}
+	verify {
+		assert!(PendingUpdates::<T, I>::get().is_empty());
+		if n > 0 {
+			let who = account::<T::AccountId>("who", n - 1, 0);
+			assert_eq!(Entries::<T, I>::get(&who), Some(n));
+		}
+	}
+
+	store_payload {
+		let l in 0 .. T::MaxPayloadLen::get();
+		let caller = funded_account::<T, I>("caller", 0);
+		let data = vec![1u8; l as usize];
+		let hash = BlakeTwo256::hash(&data);
+	}: _(RawOrigin::Signed(caller), data)
+	verify {
+		assert!(Payloads::<T, I>::contains_key(&hash));
+	}
+
+	remove_payload {
+		let l in 0 .. T::MaxPayloadLen::get();
+		let caller = funded_account::<T, I>("caller", 0);
+		let data = vec![1u8; l as usize];
+		let hash = BlakeTwo256::hash(&data);
+		Template::<T, I>::store_payload(RawOrigin::Signed(caller.clone()).into(), data)?;
+	}: _(RawOrigin::Signed(caller), hash)
+	verify {
+		assert!(!Payloads::<T, I>::contains_key(&hash));
+	}
+
+	// Only measure the overhead of `free_call` itself, as the weight of the inner call is added
+	// on top of it. `force_clear` from a signed origin fails at its origin check without any
+	// further work.
+	free_call {
+		let caller: T::AccountId = whitelisted_caller();
+		Entries::<T, I>::insert(&caller, 42);
+		let call = Box::new(Call::<T, I>::force_clear(T::Lookup::unlookup(caller.clone())));
+	}: _(RawOrigin::Signed(caller.clone()), call)
+	verify {
+		assert_eq!(Template::<T, I>::free_calls_left(&caller), T::FreeCallsPerEra::get() - 1);
+		let result = Err(DispatchError::BadOrigin);
+		assert_last_event::<T, I>(Event::FreeCallExecuted { who: caller, result }.into());
+	}
+
+	set_authorities {
+		let n in 0 .. T::MaxAuthorities::get();
+		let authorities = (0 .. n)
+			.map(|i| account("authority", i, 0))
+			.collect::<Vec<T::AccountId>>();
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, authorities)
+	verify {
+		assert_eq!(Authorities::<T, I>::get().len(), n as usize);
+	}
+
+	submit_total_signed {
+		let caller: T::AccountId = whitelisted_caller();
+		Template::<T, I>::set_authorities(
+			T::AdminOrigin::successful_origin(),
+			vec![caller.clone()],
+		)?;
+	}: _(RawOrigin::Signed(caller), 42)
+	verify {
+		assert_eq!(EntryTotal::<T, I>::get(), Some(42));
+	}
+
+	submit_total_unsigned_with_signed_payload {
+		let public = <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic
+			::generate_pair(None);
+		let public: T::Public =
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(public)
+				.into();
+		let payload = TotalPayload { block_number: Zero::zero(), total: 42, public };
+		let signature = <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::sign(
+			&payload.encode(),
+			payload.public.clone(),
+		).expect("the key was just generated; qed");
+		Template::<T, I>::set_authorities(
+			T::AdminOrigin::successful_origin(),
+			vec![payload.public.clone().into_account()],
+		)?;
+	}: _(RawOrigin::None, payload, signature)
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert_eq!(EntryTotal::<T, I>::get(), Some(42));
 	}
 }
 
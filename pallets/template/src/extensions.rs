@@ -0,0 +1,197 @@
+//! Signed extensions for the template pallet.
+
+use crate::{Call, Config, Entries, Pallet};
+use codec::{Decode, Encode};
+use frame_support::traits::{IsSubType, PalletInfoAccess};
+use sp_runtime::{
+	traits::{DispatchInfoOf, SignedExtension, Zero},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+};
+use sp_std::{fmt, marker::PhantomData, prelude::*};
+
+/// The `InvalidTransaction::Custom` code of a transaction rejected by [`CheckTemplateRateLimit`].
+pub const RATE_LIMITED: u8 = 1;
+/// The `InvalidTransaction::Custom` code of a transaction rejected by [`CheckFreeQuota`].
+pub const FREE_QUOTA_EXHAUSTED: u8 = 2;
+/// The `InvalidTransaction::Custom` code of a free call by an account that owns no entry.
+pub const FREE_CALL_NOT_ALLOWED: u8 = 3;
+/// The `InvalidTransaction::Custom` code of a free call that is longer than `MaxFreeCallLen`.
+pub const FREE_CALL_TOO_LARGE: u8 = 4;
+
+/// Reject transactions that call into the template pallet once the signer has used up its
+/// `MaxCallsPerWindow` calls for the current `RateLimitWindow`.
+///
+/// The calls are counted by the pallet when they are dispatched, which also covers calls
+/// wrapped in batches, proxies or multisig operations. This extension only looks at the
+/// top-level call, so that such transactions are kept out of the pool and blocks up front
+/// instead of failing at dispatch. Add it once for every instance of the pallet. Calls into
+/// other pallets are not affected.
+///
+/// The check only sees the calls counted on chain, so a transaction provides a tag for the
+/// signer's next call in the window. The pool keeps one transaction per tag, so the signer can
+/// have a single call into the pallet waiting in the pool at a time, and can't fill the pool
+/// with calls that would fail at dispatch. More calls per transaction can be made in a batch.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct CheckTemplateRateLimit<T: Config<I> + Send + Sync, I: 'static = ()>(
+	PhantomData<(T, I)>,
+);
+
+impl<T: Config<I> + Send + Sync, I: 'static> CheckTemplateRateLimit<T, I> {
+	/// Create a new instance of the extension.
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config<I> + Send + Sync, I: 'static> Default for CheckTemplateRateLimit<T, I> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config<I> + Send + Sync, I: 'static> fmt::Debug for CheckTemplateRateLimit<T, I> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "CheckTemplateRateLimit")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config<I> + Send + Sync, I: 'static + Send + Sync> SignedExtension
+	for CheckTemplateRateLimit<T, I>
+where
+	<T as frame_system::Config>::Call: IsSubType<Call<T, I>>,
+{
+	const IDENTIFIER: &'static str = "CheckTemplateRateLimit";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		if <Self::Call as IsSubType<Call<T, I>>>::is_sub_type(call).is_none() {
+			return Ok(ValidTransaction::default())
+		}
+		let (window_start, calls) = Pallet::<T, I>::ensure_within_rate_limit(who)?;
+		let name = <Pallet<T, I> as PalletInfoAccess>::name();
+		Ok(ValidTransaction {
+			provides: vec![(name, who, window_start, calls).encode()],
+			..Default::default()
+		})
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<(), TransactionValidityError> {
+		if <Self::Call as IsSubType<Call<T, I>>>::is_sub_type(call).is_some() {
+			Pallet::<T, I>::ensure_within_rate_limit(who)?;
+		}
+		Ok(())
+	}
+}
+
+/// Only admit `free_call` transactions while the signer has free calls left in the current era.
+///
+/// `free_call` is declared with `Pays::No`, so `ChargeTransactionPayment` lets it through without
+/// withdrawing a fee, not even the length fee. This extension keeps such transactions bounded by
+/// the signer's quota before they enter the pool or a block: the signer must own an entry, and
+/// the wrapped call must not be longer than `MaxFreeCallLen`. Other calls are not affected.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct CheckFreeQuota<T: Config<I> + Send + Sync, I: 'static = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I> + Send + Sync, I: 'static> CheckFreeQuota<T, I> {
+	/// Create a new instance of the extension.
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config<I> + Send + Sync, I: 'static> Default for CheckFreeQuota<T, I> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config<I> + Send + Sync, I: 'static> fmt::Debug for CheckFreeQuota<T, I> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "CheckFreeQuota")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config<I> + Send + Sync, I: 'static + Send + Sync> SignedExtension for CheckFreeQuota<T, I>
+where
+	<T as frame_system::Config>::Call: IsSubType<Call<T, I>>,
+{
+	const IDENTIFIER: &'static str = "CheckFreeQuota";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		let template_call = <Self::Call as IsSubType<Call<T, I>>>::is_sub_type(call);
+		if let Some(Call::free_call(inner)) = template_call {
+			if matches!(**inner, Call::free_call(..)) {
+				return Err(InvalidTransaction::Call.into())
+			}
+			if inner.encoded_size() as u32 > T::MaxFreeCallLen::get() {
+				return Err(InvalidTransaction::Custom(FREE_CALL_TOO_LARGE).into())
+			}
+			if !<Entries<T, I>>::contains_key(who) {
+				return Err(InvalidTransaction::Custom(FREE_CALL_NOT_ALLOWED).into())
+			}
+			if Pallet::<T, I>::free_calls_left(who).is_zero() {
+				return Err(InvalidTransaction::Custom(FREE_QUOTA_EXHAUSTED).into())
+			}
+		}
+		Ok(ValidTransaction::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<(), TransactionValidityError> {
+		// The quota itself is used up by `free_call`, so that transactions later in the block
+		// see the updated count.
+		self.validate(who, call, info, len).map(|_| ())
+	}
+}
@@ -14,90 +14,1084 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod extensions;
+pub mod migrations;
+pub mod weights;
+
+use codec::{Decode, Encode};
+use frame_system::offchain::{SignedPayload, SigningTypes};
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::RuntimeDebug;
+pub use extensions::{CheckFreeQuota, CheckTemplateRateLimit};
+pub use weights::WeightInfo;
+
+/// Defines application identifier for crypto keys of this module.
+///
+/// Every module that deals with signatures needs to declare its unique identifier for its crypto
+/// keys. When an offchain worker is signing transactions it's going to request keys of type
+/// `KeyTypeId` from the keystore and use the ones it finds to sign the transaction. The keys can
+/// be inserted manually via RPC (see `author_insertKey`).
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"tmpl");
+
+/// Based on the above `KeyTypeId` we need to generate a pallet-specific crypto type wrapper.
+/// We can use from supported crypto kinds (`sr25519`, `ed25519` and `ecdsa`) and augment
+/// the types with this pallet-specific identifier.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	/// The identifier used by the offchain worker to sign transactions.
+	pub struct TemplateAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for TemplateAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// Application identifier for the crypto keys of a second instance of this module.
+///
+/// Each instance submits its totals from the account of its own key. Giving the second instance
+/// its own key type keeps the two offchain workers from signing with the same account, where
+/// their transactions would compete for the same nonce.
+pub const SECOND_KEY_TYPE: KeyTypeId = KeyTypeId(*b"tmp2");
+
+/// Crypto type wrapper for `SECOND_KEY_TYPE`, see `crypto`.
+pub mod second_crypto {
+	use super::SECOND_KEY_TYPE;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, SECOND_KEY_TYPE);
+
+	/// The identifier used by the offchain worker of the second instance to sign transactions.
+	pub struct SecondTemplateAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for SecondTemplateAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// The global value together with the block in which it was last written.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct StoredValue<BlockNumber> {
+	/// The stored value.
+	pub value: u32,
+	/// The block in which `value` was last written.
+	pub updated_at: BlockNumber,
+}
+
+/// A stored payload together with its owner and the deposit held for it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct StoredPayload<AccountId, Balance, Data> {
+	/// The account that stored the payload.
+	pub owner: AccountId,
+	/// The amount reserved from `owner` for storing the payload.
+	pub deposit: Balance,
+	/// The payload itself.
+	pub data: Data,
+}
+
+/// The payload of an unsigned transaction submitted by the offchain worker.
+///
+/// The payload is signed with a `KEY_TYPE` key, so the transaction can be validated without
+/// being paid for by an account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TotalPayload<Public, BlockNumber> {
+	/// The block in which the total was computed.
+	pub block_number: BlockNumber,
+	/// The sum of all entries.
+	pub total: u32,
+	/// The key that signed the payload.
+	pub public: Public,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for TotalPayload<T::Public, T::BlockNumber> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
-	use frame_system::pallet_prelude::*;
+	use super::{StoredPayload, StoredValue, TotalPayload, WeightInfo};
+	use frame_support::{
+		dispatch::{
+			DispatchResult, DispatchResultWithPostInfo, Dispatchable, PostDispatchInfo,
+			WithPostDispatchInfo,
+		},
+		pallet_prelude::*,
+		traits::{
+			BalanceStatus, Currency, NamedReservableCurrency, PalletInfoAccess, StorageVersion,
+		},
+		weights::GetDispatchInfo,
+	};
+	use frame_system::{
+		offchain::{
+			AppCrypto, CreateSignedTransaction, SendSignedTransaction, SendUnsignedTransaction,
+			SignedPayload, Signer,
+		},
+		pallet_prelude::*,
+	};
+	use sp_runtime::{
+		traits::{BlakeTwo256, Hash, IdentifyAccount, Saturating, StaticLookup, Zero},
+		transaction_validity::TransactionPriority,
+		Perbill,
+	};
+	use sp_core::H256;
+	use sp_std::{convert::TryFrom, prelude::*};
+
+	/// The balance type of the currency in which storage deposits are held.
+	pub type BalanceOf<T, I = ()> =
+		<<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config<I: 'static = ()>:
+		CreateSignedTransaction<Call<Self, I>> + frame_system::Config
+	{
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
-		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The overarching call type.
+		///
+		/// `free_call` dispatches through it, so that the call filter of the origin applies to
+		/// the wrapped call.
+		type Call: Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+			+ From<Call<Self, I>>;
+		/// The currency in which storage deposits are held.
+		type Currency: NamedReservableCurrency<Self::AccountId, ReserveIdentifier = [u8; 8]>;
+		/// The identifier under which storage deposits are reserved.
+		///
+		/// Each instance of the pallet should use its own identifier.
+		#[pallet::constant]
+		type ReserveId: Get<[u8; 8]>;
+		/// The amount reserved from an account for each entry it stores.
+		#[pallet::constant]
+		type StorageDeposit: Get<BalanceOf<Self, I>>;
+		/// The origin which may overwrite or remove any entry.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+		/// The identifier type for an offchain worker.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+		/// The maximum number of accounts that may report the total of all entries.
+		#[pallet::constant]
+		type MaxAuthorities: Get<u32>;
+		/// The minimum number of blocks between two unsigned transactions.
+		///
+		/// This ensures that we only accept unsigned transactions once every `UnsignedInterval`
+		/// blocks.
+		#[pallet::constant]
+		type UnsignedInterval: Get<Self::BlockNumber>;
+		/// A configuration for base priority of unsigned transactions.
+		///
+		/// This is exposed so that it can be tuned for particular runtime, when multiple pallets
+		/// send unsigned transactions.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+		/// The number of blocks between two runs of the update queue.
+		#[pallet::constant]
+		type ProcessingPeriod: Get<Self::BlockNumber>;
+		/// The maximum number of entry updates waiting in the queue.
+		///
+		/// This bounds the weight of a single run of the queue.
+		#[pallet::constant]
+		type MaxQueueLen: Get<u32>;
+		/// The maximum length of a stored payload, in bytes.
+		#[pallet::constant]
+		type MaxPayloadLen: Get<u32>;
+		/// The amount reserved for each byte of a stored payload, on top of `StorageDeposit`.
+		#[pallet::constant]
+		type PayloadByteDeposit: Get<BalanceOf<Self, I>>;
+		/// The number of blocks in a rate limit window.
+		///
+		/// See [`CheckTemplateRateLimit`](crate::CheckTemplateRateLimit).
+		#[pallet::constant]
+		type RateLimitWindow: Get<Self::BlockNumber>;
+		/// The maximum number of calls into this pallet an account can make per window.
+		#[pallet::constant]
+		type MaxCallsPerWindow: Get<u32>;
+		/// The number of blocks in a free quota era.
+		///
+		/// See [`CheckFreeQuota`](crate::CheckFreeQuota).
+		#[pallet::constant]
+		type FreeQuotaEra: Get<Self::BlockNumber>;
+		/// The number of fee-free calls each account can make per era through `free_call`.
+		#[pallet::constant]
+		type FreeCallsPerEra: Get<u32>;
+		/// The maximum encoded length of a call wrapped in `free_call`, in bytes.
+		///
+		/// No length fee is charged for a free call either, so this bounds the data an account
+		/// can put into blocks for free.
+		#[pallet::constant]
+		type MaxFreeCallLen: Get<u32>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
-	pub struct Pallet<T>(_);
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
 	// The pallet's runtime storage items.
 	// https://substrate.dev/docs/en/knowledgebase/runtime/storage
 	#[pallet::storage]
-	#[pallet::getter(fn something)]
 	// Learn more about declaring storage items:
 	// https://substrate.dev/docs/en/knowledgebase/runtime/storage#declaring-storage-items
-	pub type Something<T> = StorageValue<_, u32>;
+	pub type Something<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, StoredValue<T::BlockNumber>>;
+
+	/// The value stored by each account. An account owns at most one entry.
+	#[pallet::storage]
+	#[pallet::getter(fn entry)]
+	pub type Entries<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32>;
+
+	/// The deposit reserved for each entry, keyed by the entry's owner.
+	///
+	/// Entries created at genesis have no deposit.
+	#[pallet::storage]
+	#[pallet::getter(fn deposit)]
+	pub type Deposits<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T, I>>;
+
+	/// The sum of all entries, as last reported by an offchain worker.
+	#[pallet::storage]
+	#[pallet::getter(fn entry_total)]
+	pub type EntryTotal<T, I = ()> = StorageValue<_, u32>;
+
+	/// Defines the block when the next unsigned transaction will be accepted.
+	///
+	/// To prevent spam of unsigned (and unpaid!) transactions on the network, we only allow one
+	/// transaction every `T::UnsignedInterval` blocks. This storage entry defines when the new
+	/// transaction is going to be accepted.
+	#[pallet::storage]
+	#[pallet::getter(fn next_unsigned_at)]
+	pub type NextUnsignedAt<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// Entry updates waiting for the next run of the queue, as `(owner, new_value)` pairs.
+	///
+	/// Each account has at most one pending update.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_updates)]
+	pub type PendingUpdates<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<(T::AccountId, u32), T::MaxQueueLen>, ValueQuery>;
+
+	/// Stored payloads, keyed by the blake2-256 hash of their data.
+	#[pallet::storage]
+	#[pallet::getter(fn payload)]
+	pub type Payloads<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Identity,
+		H256,
+		StoredPayload<T::AccountId, BalanceOf<T, I>, BoundedVec<u8, T::MaxPayloadLen>>,
+	>;
+
+	/// The number of calls each account made in its latest rate limit window, as
+	/// `(window_start, calls)`.
+	#[pallet::storage]
+	#[pallet::getter(fn rate_limit)]
+	pub type RateLimits<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (T::BlockNumber, u32), ValueQuery>;
+
+	/// The number of free calls each account made in its latest free quota era, as
+	/// `(era_start, calls)`.
+	#[pallet::storage]
+	pub type FreeCallsUsed<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (T::BlockNumber, u32), ValueQuery>;
+
+	/// The accounts whose offchain workers may report the total of all entries.
+	#[pallet::storage]
+	#[pallet::getter(fn authorities)]
+	pub type Authorities<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxAuthorities>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
+		/// The initial global value.
+		pub something: Option<u32>,
+		/// The initial entries, as `(owner, value)` pairs.
+		pub entries: Vec<(T::AccountId, u32)>,
+		/// The accounts whose offchain workers may report the total of all entries.
+		pub authorities: Vec<T::AccountId>,
+		pub phantom: PhantomData<I>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config<I>, I: 'static> Default for GenesisConfig<T, I> {
+		fn default() -> Self {
+			Self {
+				something: None,
+				entries: Vec::new(),
+				authorities: Vec::new(),
+				phantom: Default::default(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config<I>, I: 'static> GenesisBuild<T, I> for GenesisConfig<T, I> {
+		fn build(&self) {
+			if let Some(value) = self.something {
+				<Something<T, I>>::put(StoredValue { value, updated_at: Default::default() });
+			}
+			for (who, value) in &self.entries {
+				assert!(!<Entries<T, I>>::contains_key(who), "Account owns more than one entry");
+				<Entries<T, I>>::insert(who, value);
+			}
+			let authorities = BoundedVec::<_, T::MaxAuthorities>::try_from(self.authorities.clone())
+				.expect("Too many authorities");
+			<Authorities<T, I>>::put(authorities);
+		}
+	}
 
 	// Pallets use events to inform users when important changes are made.
 	// https://substrate.dev/docs/en/knowledgebase/runtime/events
+	//
+	// Events are deposited under the topics returned by `Pallet::account_topic` and
+	// `Pallet::value_topic` for every account and value they refer to, see `deposit_event`.
 	#[pallet::event]
 	#[pallet::metadata(T::AccountId = "AccountId")]
-	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event<T: Config> {
-		/// Event documentation should end with an array that provides descriptive names for event
-		/// parameters. [something, who]
-		SomethingStored(u32, T::AccountId),
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// An account stored a new global value.
+		SomethingStored { value: u32, who: T::AccountId },
+		/// An account set the value of its entry.
+		EntrySet { who: T::AccountId, value: u32 },
+		/// An account incremented the value of its entry.
+		EntryIncremented { who: T::AccountId, value: u32 },
+		/// An account removed its entry.
+		EntryCleared { who: T::AccountId },
+		/// An entry was handed over to a new owner.
+		EntryTransferred { from: T::AccountId, to: T::AccountId },
+		/// An offchain worker reported the sum of all entries.
+		TotalStored { total: u32 },
+		/// The admin origin set the value of an account's entry.
+		EntryForceSet { who: T::AccountId, value: u32 },
+		/// The admin origin removed an account's entry.
+		EntryForceCleared { who: T::AccountId },
+		/// The admin origin removed all stored values and this many entries.
+		AllKilled { entries: u32 },
+		/// An account queued an update of its entry.
+		EntryUpdateQueued { who: T::AccountId, value: u32 },
+		/// The update queue was processed. Updates whose entry no longer exists are skipped.
+		QueueProcessed { applied: u32, skipped: u32 },
+		/// An account stored a payload.
+		PayloadStored { hash: H256, who: T::AccountId },
+		/// An account removed a payload it stored.
+		PayloadRemoved { hash: H256, who: T::AccountId },
+		/// An account used its free quota to dispatch a call, which returned `result`.
+		FreeCallExecuted { who: T::AccountId, result: DispatchResult },
+		/// The accounts that may report the total of all entries were replaced.
+		AuthoritiesSet { authorities: u32 },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		/// Run the update queue every `ProcessingPeriod` blocks.
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let period = T::ProcessingPeriod::get();
+			if period.is_zero() || !(n % period).is_zero() {
+				return 0
+			}
+			Self::process_queue()
+		}
+
+		fn integrity_test() {
+			assert!(!T::ProcessingPeriod::get().is_zero(), "`ProcessingPeriod` must not be zero");
+			assert!(!T::RateLimitWindow::get().is_zero(), "`RateLimitWindow` must not be zero");
+			assert!(!T::FreeQuotaEra::get().is_zero(), "`FreeQuotaEra` must not be zero");
+			// A full queue is processed at the start of a block, before any extrinsic, so it
+			// must leave most of the block to them.
+			let max_weight = T::WeightInfo::process_queue(T::MaxQueueLen::get());
+			let limit = Perbill::from_percent(10) * T::BlockWeights::get().max_block;
+			assert!(
+				max_weight <= limit,
+				"processing a full queue takes {} weight, more than the limit of {}",
+				max_weight,
+				limit,
+			);
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::v1::migrate::<T, I>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			crate::migrations::v1::pre_migrate::<T, I>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			crate::migrations::v1::post_migrate::<T, I>()
+		}
+
+		/// Offchain worker entry point.
+		///
+		/// Sums up all entries and reports the result back on chain, either as a signed
+		/// transaction or as an unsigned transaction with a signed payload. Nothing is sent if the
+		/// total did not change or if the local keystore holds no `KEY_TYPE` key.
+		fn offchain_worker(block_number: T::BlockNumber) {
+			let total = Self::compute_total();
+			if Self::entry_total() == Some(total) {
+				return
+			}
+
+			let res = match Self::choose_transaction_type(block_number) {
+				TransactionType::Signed => Self::submit_total_signed_transaction(total),
+				TransactionType::UnsignedWithSignedPayload =>
+					Self::submit_total_unsigned_transaction(block_number, total),
+				TransactionType::None => Ok(()),
+			};
+			if let Err(e) = res {
+				log::debug!(target: "runtime::template", "offchain worker error: {}", e);
+			}
+		}
 	}
 
 	// Errors inform users that something went wrong.
 	#[pallet::error]
-	pub enum Error<T> {
+	pub enum Error<T, I = ()> {
 		/// Error names should be descriptive.
 		NoneValue,
 		/// Errors should have helpful documentation associated with them.
 		StorageOverflow,
+		/// The account does not own an entry.
+		NoEntry,
+		/// The destination account already owns an entry.
+		EntryExists,
+		/// The update queue is full until the next run.
+		QueueFull,
+		/// The payload is longer than `MaxPayloadLen`.
+		PayloadTooLarge,
+		/// A payload with the same hash is already stored.
+		PayloadExists,
+		/// No payload is stored under the given hash.
+		NoPayload,
+		/// The payload was stored by another account.
+		NotPayloadOwner,
+		/// The account has no free calls left in the current era.
+		FreeQuotaExhausted,
+		/// `free_call` cannot dispatch another `free_call`.
+		NestedFreeCall,
+		/// The call wrapped in `free_call` is longer than `MaxFreeCallLen`.
+		FreeCallTooLarge,
+		/// The account made `MaxCallsPerWindow` calls into the pallet in the current window.
+		RateLimited,
+		/// The account may not report the total of all entries.
+		NotAuthority,
+		/// More than `MaxAuthorities` authorities were given.
+		TooManyAuthorities,
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config<I>, I: 'static> ValidateUnsigned for Pallet<T, I> {
+		type Call = Call<T, I>;
+
+		/// Validate unsigned call to this module.
+		///
+		/// By default unsigned transactions are disallowed, but implementing the validator
+		/// here we make sure that some particular calls (the ones produced by offchain worker)
+		/// are being whitelisted and marked as valid.
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			if let Call::submit_total_unsigned_with_signed_payload(ref payload, ref signature) =
+				call
+			{
+				let signature_valid =
+					SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone());
+				if !signature_valid {
+					return InvalidTransaction::BadProof.into()
+				}
+				if !Self::is_authority(&payload.public.clone().into_account()) {
+					return InvalidTransaction::BadSigner.into()
+				}
+				Self::validate_transaction_parameters(&payload.block_number)
+			} else {
+				InvalidTransaction::Call.into()
+			}
+		}
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
 	// These functions materialize as "extrinsics", which are often compared to transactions.
-	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
+	// Dispatchable functions must be annotated with a weight and must return a DispatchResult,
+	// optionally together with the weight that was actually used.
 	#[pallet::call]
-	impl<T: Config> Pallet<T> {
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// An example dispatchable that takes a singles value as a parameter, writes the value to
 		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn do_something(origin: OriginFor<T>, something: u32) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::do_something())]
+		pub fn do_something(origin: OriginFor<T>, something: u32) -> DispatchResultWithPostInfo {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://substrate.dev/docs/en/knowledgebase/runtime/origin
 			let who = ensure_signed(origin)?;
+			Self::note_rate_limited_call(&who)?;
 
 			// Update storage.
-			<Something<T>>::put(something);
+			let updated_at = <frame_system::Pallet<T>>::block_number();
+			<Something<T, I>>::put(StoredValue { value: something, updated_at });
 
 			// Emit an event.
-			Self::deposit_event(Event::SomethingStored(something, who));
+			Self::deposit_event(Event::SomethingStored { value: something, who });
 			// Return a successful DispatchResultWithPostInfo
-			Ok(())
+			Ok(().into())
 		}
 
 		/// An example dispatchable that may throw a custom error.
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
-		pub fn cause_error(origin: OriginFor<T>) -> DispatchResult {
-			let _who = ensure_signed(origin)?;
+		#[pallet::weight(T::WeightInfo::cause_error().max(T::WeightInfo::cause_error_none_value()))]
+		pub fn cause_error(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::note_rate_limited_call(&who)?;
 
+			// Both error paths stop after a single read, so only charge for that.
+			let read_only = T::WeightInfo::cause_error_none_value();
 			// Read a value from storage.
-			match <Something<T>>::get() {
+			match <Something<T, I>>::get() {
 				// Return an error if the value has not been set.
-				None => Err(Error::<T>::NoneValue)?,
+				None => Err(Error::<T, I>::NoneValue.with_weight(read_only)),
 				Some(old) => {
 					// Increment the value read from storage; will error in the event of overflow.
-					let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+					let new = old
+						.value
+						.checked_add(1)
+						.ok_or(Error::<T, I>::StorageOverflow.with_weight(read_only))?;
 					// Update the value in storage with the incremented result.
-					<Something<T>>::put(new);
-					Ok(())
+					let updated_at = <frame_system::Pallet<T>>::block_number();
+					<Something<T, I>>::put(StoredValue { value: new, updated_at });
+					Ok(Some(T::WeightInfo::cause_error()).into())
 				},
 			}
 		}
+
+		/// Create or overwrite the entry owned by the signer.
+		///
+		/// `StorageDeposit` is reserved from the signer unless the entry is already backed by a
+		/// deposit.
+		#[pallet::weight(T::WeightInfo::set_entry())]
+		pub fn set_entry(origin: OriginFor<T>, value: u32) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::note_rate_limited_call(&who)?;
+
+			if !<Deposits<T, I>>::contains_key(&who) {
+				let deposit = T::StorageDeposit::get();
+				T::Currency::reserve_named(&T::ReserveId::get(), &who, deposit)?;
+				<Deposits<T, I>>::insert(&who, deposit);
+			}
+			<Entries<T, I>>::insert(&who, value);
+
+			Self::deposit_event(Event::EntrySet { who, value });
+			Ok(().into())
+		}
+
+		/// Increment the entry owned by the signer by one.
+		#[pallet::weight(T::WeightInfo::increment_entry())]
+		pub fn increment_entry(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::note_rate_limited_call(&who)?;
+
+			let new =
+				<Entries<T, I>>::try_mutate(&who, |maybe_value| -> Result<u32, DispatchError> {
+					let value = maybe_value.as_mut().ok_or(Error::<T, I>::NoEntry)?;
+					*value = value.checked_add(1).ok_or(Error::<T, I>::StorageOverflow)?;
+					Ok(*value)
+				})?;
+
+			Self::deposit_event(Event::EntryIncremented { who, value: new });
+			Ok(().into())
+		}
+
+		/// Remove the entry owned by the signer and refund its deposit.
+		#[pallet::weight(T::WeightInfo::clear_entry())]
+		pub fn clear_entry(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::note_rate_limited_call(&who)?;
+
+			ensure!(<Entries<T, I>>::contains_key(&who), Error::<T, I>::NoEntry);
+			<Entries<T, I>>::remove(&who);
+			Self::refund_deposit(&who);
+
+			Self::deposit_event(Event::EntryCleared { who });
+			Ok(().into())
+		}
+
+		/// Hand the entry owned by the signer over to `new_owner`.
+		///
+		/// The destination must not already own an entry. The entry's deposit moves along with
+		/// it and is held in reserve on the destination account, which must already exist.
+		#[pallet::weight(T::WeightInfo::transfer_entry())]
+		pub fn transfer_entry(
+			origin: OriginFor<T>,
+			new_owner: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::note_rate_limited_call(&who)?;
+			let new_owner = T::Lookup::lookup(new_owner)?;
+
+			ensure!(!<Entries<T, I>>::contains_key(&new_owner), Error::<T, I>::EntryExists);
+			let value = <Entries<T, I>>::get(&who).ok_or(Error::<T, I>::NoEntry)?;
+			// Move the deposit before touching storage, so that nothing changes if the destination
+			// can't hold it.
+			if let Some(deposit) = <Deposits<T, I>>::get(&who) {
+				let remaining = T::Currency::repatriate_reserved_named(
+					&T::ReserveId::get(),
+					&who,
+					&new_owner,
+					deposit,
+					BalanceStatus::Reserved,
+				)?;
+				<Deposits<T, I>>::remove(&who);
+				<Deposits<T, I>>::insert(&new_owner, deposit.saturating_sub(remaining));
+			}
+			<Entries<T, I>>::remove(&who);
+			<Entries<T, I>>::insert(&new_owner, value);
+
+			Self::deposit_event(Event::EntryTransferred { from: who, to: new_owner });
+			Ok(().into())
+		}
+
+		/// Queue an update of the entry owned by the signer.
+		///
+		/// The entry is set to `value` at the next run of the queue, provided it still exists by
+		/// then. An update already queued by the signer is replaced, so that a single account
+		/// can't fill the queue.
+		#[pallet::weight(T::WeightInfo::queue_entry_update())]
+		pub fn queue_entry_update(origin: OriginFor<T>, value: u32) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::note_rate_limited_call(&who)?;
+
+			ensure!(<Entries<T, I>>::contains_key(&who), Error::<T, I>::NoEntry);
+			let mut queue = <PendingUpdates<T, I>>::get().into_inner();
+			match queue.iter_mut().find(|(owner, _)| *owner == who) {
+				Some(pending) => pending.1 = value,
+				None => queue.push((who.clone(), value)),
+			}
+			let queue = BoundedVec::<_, T::MaxQueueLen>::try_from(queue)
+				.map_err(|_| Error::<T, I>::QueueFull)?;
+			<PendingUpdates<T, I>>::put(queue);
+
+			Self::deposit_event(Event::EntryUpdateQueued { who, value });
+			Ok(().into())
+		}
+
+		/// Store `data` under its blake2-256 hash.
+		///
+		/// `StorageDeposit` plus `PayloadByteDeposit` for each byte of `data` is reserved from
+		/// the signer until the payload is removed.
+		#[pallet::weight(T::WeightInfo::store_payload(data.len() as u32))]
+		pub fn store_payload(origin: OriginFor<T>, data: Vec<u8>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::note_rate_limited_call(&who)?;
+
+			let data = BoundedVec::<u8, T::MaxPayloadLen>::try_from(data)
+				.map_err(|_| Error::<T, I>::PayloadTooLarge)?;
+			let hash = BlakeTwo256::hash(&data);
+			ensure!(!<Payloads<T, I>>::contains_key(&hash), Error::<T, I>::PayloadExists);
+
+			let deposit = T::PayloadByteDeposit::get()
+				.saturating_mul((data.len() as u32).into())
+				.saturating_add(T::StorageDeposit::get());
+			T::Currency::reserve_named(&T::ReserveId::get(), &who, deposit)?;
+			<Payloads<T, I>>::insert(&hash, StoredPayload { owner: who.clone(), deposit, data });
+
+			Self::deposit_event(Event::PayloadStored { hash, who });
+			Ok(().into())
+		}
+
+		/// Remove a payload stored by the signer and refund its deposit.
+		///
+		/// The length of the payload is only known once it is read, so the weight assumes a
+		/// payload of `MaxPayloadLen` bytes and the difference is refunded afterwards.
+		#[pallet::weight(T::WeightInfo::remove_payload(T::MaxPayloadLen::get()))]
+		pub fn remove_payload(origin: OriginFor<T>, hash: H256) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::note_rate_limited_call(&who)?;
+
+			let payload = <Payloads<T, I>>::get(&hash).ok_or(Error::<T, I>::NoPayload)?;
+			ensure!(payload.owner == who, Error::<T, I>::NotPayloadOwner);
+			<Payloads<T, I>>::remove(&hash);
+			T::Currency::unreserve_named(&T::ReserveId::get(), &who, payload.deposit);
+
+			Self::deposit_event(Event::PayloadRemoved { hash, who });
+			Ok(Some(T::WeightInfo::remove_payload(payload.data.len() as u32)).into())
+		}
+
+		/// Dispatch `call` from the signer without charging a transaction fee.
+		///
+		/// Only accounts that own an entry can make free calls, so that new accounts don't get a
+		/// quota for nothing, and the encoded `call` must not be longer than `MaxFreeCallLen`.
+		/// Each account can make `FreeCallsPerEra` such calls per era. The quota is used up even
+		/// if `call` fails; its result is reported in the `FreeCallExecuted` event. `call` is
+		/// subject to the same call filters as the signer's origin, e.g. those of a proxy.
+		#[pallet::weight({
+			let info = call.get_dispatch_info();
+			(T::WeightInfo::free_call().saturating_add(info.weight), info.class, Pays::No)
+		})]
+		pub fn free_call(
+			origin: OriginFor<T>,
+			call: Box<Call<T, I>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin.clone())?;
+
+			ensure!(!matches!(*call, Call::free_call(..)), Error::<T, I>::NestedFreeCall);
+			ensure!(
+				call.encoded_size() as u32 <= T::MaxFreeCallLen::get(),
+				Error::<T, I>::FreeCallTooLarge
+			);
+			ensure!(<Entries<T, I>>::contains_key(&who), Error::<T, I>::NoEntry);
+			let calls_left = Self::free_calls_left(&who);
+			ensure!(calls_left > 0, Error::<T, I>::FreeQuotaExhausted);
+			let calls = T::FreeCallsPerEra::get() - calls_left + 1;
+			<FreeCallsUsed<T, I>>::insert(&who, (Self::current_quota_era(), calls));
+
+			let info = call.get_dispatch_info();
+			let call = <T as Config<I>>::Call::from(*call);
+			let (result, call_weight) = match call.dispatch(origin) {
+				Ok(post_info) => (Ok(()), post_info.calc_actual_weight(&info)),
+				Err(err) => (Err(err.error), err.post_info.calc_actual_weight(&info)),
+			};
+
+			Self::deposit_event(Event::FreeCallExecuted { who, result });
+			Ok(PostDispatchInfo {
+				actual_weight: Some(T::WeightInfo::free_call().saturating_add(call_weight)),
+				pays_fee: Pays::No,
+			})
+		}
+
+		/// Set the entry owned by `who`, bypassing the owner.
+		///
+		/// No deposit is taken; a deposit already backing the entry is left in place.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[pallet::weight(T::WeightInfo::force_set())]
+		pub fn force_set(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			value: u32,
+		) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			<Entries<T, I>>::insert(&who, value);
+
+			Self::deposit_event(Event::EntryForceSet { who, value });
+			Ok(().into())
+		}
+
+		/// Remove the entry owned by `who` and refund its deposit to the owner.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[pallet::weight(T::WeightInfo::force_clear())]
+		pub fn force_clear(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			ensure!(<Entries<T, I>>::contains_key(&who), Error::<T, I>::NoEntry);
+			<Entries<T, I>>::remove(&who);
+			Self::refund_deposit(&who);
+
+			Self::deposit_event(Event::EntryForceCleared { who });
+			Ok(().into())
+		}
+
+		/// Remove the global value, the reported total, all queued entry updates and up to `limit`
+		/// entries.
+		///
+		/// Deposits of removed entries are refunded to their owners. Call again until
+		/// `AllKilled` reports fewer than `limit` entries to be sure the map is empty. Only the
+		/// entries actually removed are charged for.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[pallet::weight(T::WeightInfo::kill_all(*limit))]
+		pub fn kill_all(origin: OriginFor<T>, limit: u32) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			<Something<T, I>>::kill();
+			<EntryTotal<T, I>>::kill();
+			<PendingUpdates<T, I>>::kill();
+			let mut removed = 0u32;
+			for (who, _) in <Entries<T, I>>::drain().take(limit as usize) {
+				Self::refund_deposit(&who);
+				removed += 1;
+			}
+
+			Self::deposit_event(Event::AllKilled { entries: removed });
+			Ok(Some(T::WeightInfo::kill_all(removed)).into())
+		}
+
+		/// Replace the accounts whose offchain workers may report the total of all entries.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[pallet::weight(T::WeightInfo::set_authorities(authorities.len() as u32))]
+		pub fn set_authorities(
+			origin: OriginFor<T>,
+			authorities: Vec<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let authorities = BoundedVec::<_, T::MaxAuthorities>::try_from(authorities)
+				.map_err(|_| Error::<T, I>::TooManyAuthorities)?;
+			let count = authorities.len() as u32;
+			<Authorities<T, I>>::put(authorities);
+
+			Self::deposit_event(Event::AuthoritiesSet { authorities: count });
+			Ok(().into())
+		}
+
+		/// Store the sum of all entries, as computed by an offchain worker.
+		///
+		/// This is submitted by the offchain worker as a regular signed transaction. The signer
+		/// must be one of the `Authorities`.
+		#[pallet::weight(T::WeightInfo::submit_total_signed())]
+		pub fn submit_total_signed(origin: OriginFor<T>, total: u32) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_authority(&who), Error::<T, I>::NotAuthority);
+			Self::note_rate_limited_call(&who)?;
+
+			<EntryTotal<T, I>>::put(total);
+
+			Self::deposit_event(Event::TotalStored { total });
+			Ok(().into())
+		}
+
+		/// Store the sum of all entries, as computed by an offchain worker.
+		///
+		/// This is submitted by the offchain worker as an unsigned transaction whose payload is
+		/// signed with a `KEY_TYPE` key. `validate_unsigned` checks the signature and that the key
+		/// belongs to one of the `Authorities` before the transaction reaches the pool.
+		#[pallet::weight(T::WeightInfo::submit_total_unsigned_with_signed_payload())]
+		pub fn submit_total_unsigned_with_signed_payload(
+			origin: OriginFor<T>,
+			payload: TotalPayload<T::Public, T::BlockNumber>,
+			_signature: T::Signature,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+
+			<EntryTotal<T, I>>::put(payload.total);
+			// Throttle the next unsigned transaction.
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			<NextUnsignedAt<T, I>>::put(current_block + T::UnsignedInterval::get());
+
+			Self::deposit_event(Event::TotalStored { total: payload.total });
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// The current global value, if one has been stored.
+		pub fn something() -> Option<u32> {
+			<Something<T, I>>::get().map(|stored| stored.value)
+		}
+
+		/// Apply all queued entry updates and return the weight consumed.
+		fn process_queue() -> Weight {
+			let queue = <PendingUpdates<T, I>>::take();
+			let len = queue.len() as u32;
+			let (mut applied, mut skipped) = (0u32, 0u32);
+			for (who, value) in queue.into_iter() {
+				if <Entries<T, I>>::contains_key(&who) {
+					<Entries<T, I>>::insert(&who, value);
+					applied += 1;
+				} else {
+					skipped += 1;
+				}
+			}
+
+			Self::deposit_event(Event::QueueProcessed { applied, skipped });
+			T::WeightInfo::process_queue(len)
+		}
+
+		/// The topic under which all events of this pallet instance that refer to `who` are
+		/// deposited.
+		pub fn account_topic(who: &T::AccountId) -> T::Hash {
+			T::Hashing::hash_of(&(<Self as PalletInfoAccess>::name(), b"account", who))
+		}
+
+		/// The topic under which all events of this pallet instance that refer to `value` are
+		/// deposited. `value` is an entry value, a total or a payload hash.
+		pub fn value_topic<V: Encode>(value: &V) -> T::Hash {
+			T::Hashing::hash_of(&(<Self as PalletInfoAccess>::name(), b"value", value))
+		}
+
+		/// Deposit `event` under the topics of the accounts and values it refers to.
+		///
+		/// Every topic is one more `EventTopics` write, which the weight of the emitting call has
+		/// to account for.
+		pub(super) fn deposit_event(event: Event<T, I>) {
+			let topics = match &event {
+				Event::SomethingStored { value, who } |
+				Event::EntrySet { who, value } |
+				Event::EntryIncremented { who, value } |
+				Event::EntryForceSet { who, value } |
+				Event::EntryUpdateQueued { who, value } =>
+					vec![Self::account_topic(who), Self::value_topic(value)],
+				Event::EntryCleared { who } | Event::EntryForceCleared { who } =>
+					vec![Self::account_topic(who)],
+				Event::EntryTransferred { from, to } =>
+					vec![Self::account_topic(from), Self::account_topic(to)],
+				Event::PayloadStored { hash, who } | Event::PayloadRemoved { hash, who } =>
+					vec![Self::account_topic(who), Self::value_topic(hash)],
+				Event::TotalStored { total } => vec![Self::value_topic(total)],
+				Event::FreeCallExecuted { who, .. } => vec![Self::account_topic(who)],
+				Event::AllKilled { .. } |
+				Event::QueueProcessed { .. } |
+				Event::AuthoritiesSet { .. } => Vec::new(),
+				// Only reachable through the hidden phantom variant.
+				_ => Vec::new(),
+			};
+			let event = <T as Config<I>>::Event::from(event);
+			<frame_system::Pallet<T>>::deposit_event_indexed(&topics, event.into());
+		}
+
+		/// Check that `who` may make another call in the current rate limit window.
+		///
+		/// Returns the start of the window and the number of calls already made in it.
+		pub(crate) fn ensure_within_rate_limit(
+			who: &T::AccountId,
+		) -> Result<(T::BlockNumber, u32), TransactionValidityError> {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let window_start = now - now % T::RateLimitWindow::get();
+			let (last_window, calls) = <RateLimits<T, I>>::get(who);
+			let calls = if last_window == window_start { calls } else { 0 };
+			if calls >= T::MaxCallsPerWindow::get() {
+				return Err(InvalidTransaction::Custom(crate::extensions::RATE_LIMITED).into())
+			}
+			Ok((window_start, calls))
+		}
+
+		/// Count a call of `who` against its rate limit.
+		///
+		/// Every signed call into the pallet is counted when it is dispatched, whether it is
+		/// submitted directly or wrapped in another call such as a batch or a proxy call. The
+		/// call is counted even if it fails later on.
+		fn note_rate_limited_call(who: &T::AccountId) -> DispatchResult {
+			let (window_start, calls) =
+				Self::ensure_within_rate_limit(who).map_err(|_| Error::<T, I>::RateLimited)?;
+			<RateLimits<T, I>>::insert(who, (window_start, calls + 1));
+			Ok(())
+		}
+
+		/// Whether the offchain worker of `who` may report the total of all entries.
+		pub fn is_authority(who: &T::AccountId) -> bool {
+			Self::authorities().contains(who)
+		}
+
+		/// The number of free calls `who` has left in the current era.
+		pub fn free_calls_left(who: &T::AccountId) -> u32 {
+			let (era_start, calls) = <FreeCallsUsed<T, I>>::get(who);
+			let calls = if era_start == Self::current_quota_era() { calls } else { 0 };
+			T::FreeCallsPerEra::get().saturating_sub(calls)
+		}
+
+		/// The first block of the free quota era that contains the current block.
+		fn current_quota_era() -> T::BlockNumber {
+			let now = <frame_system::Pallet<T>>::block_number();
+			now - now % T::FreeQuotaEra::get()
+		}
+
+		/// Release the deposit backing the entry of `who`, if there is one.
+		fn refund_deposit(who: &T::AccountId) {
+			if let Some(deposit) = <Deposits<T, I>>::take(who) {
+				T::Currency::unreserve_named(&T::ReserveId::get(), who, deposit);
+			}
+		}
+
+		/// The sum of all entries, saturating at `u32::MAX`.
+		fn compute_total() -> u32 {
+			<Entries<T, I>>::iter_values().fold(0u32, |total, value| total.saturating_add(value))
+		}
+
+		/// Alternate between signed and unsigned transactions from one block to the next.
+		///
+		/// Unsigned transactions are only chosen when the chain would accept one.
+		fn choose_transaction_type(block_number: T::BlockNumber) -> TransactionType {
+			if (block_number % 2u32.into()).is_zero() {
+				TransactionType::Signed
+			} else if Self::next_unsigned_at() <= block_number {
+				TransactionType::UnsignedWithSignedPayload
+			} else {
+				TransactionType::None
+			}
+		}
+
+		/// Report `total` in a transaction signed by a local `KEY_TYPE` key.
+		fn submit_total_signed_transaction(total: u32) -> Result<(), &'static str> {
+			let signer = Signer::<T, T::AuthorityId>::any_account();
+			if !signer.can_sign() {
+				return Err(
+					"No local accounts available. Consider adding one via `author_insertKey` RPC.",
+				)
+			}
+
+			let (_, result) = signer
+				.send_signed_transaction(|_account| Call::submit_total_signed(total))
+				.ok_or("No local accounts available.")?;
+			result.map_err(|()| "Unable to submit transaction")
+		}
+
+		/// Report `total` in an unsigned transaction whose payload is signed by a local `KEY_TYPE`
+		/// key.
+		fn submit_total_unsigned_transaction(
+			block_number: T::BlockNumber,
+			total: u32,
+		) -> Result<(), &'static str> {
+			let (_, result) = Signer::<T, T::AuthorityId>::any_account()
+				.send_unsigned_transaction(
+					|account| TotalPayload { block_number, total, public: account.public.clone() },
+					|payload, signature| {
+						Call::submit_total_unsigned_with_signed_payload(payload, signature)
+					},
+				)
+				.ok_or("No local accounts available.")?;
+			result.map_err(|()| "Unable to submit transaction")
+		}
+
+		/// Check that an unsigned transaction computed in `block_number` can be accepted now.
+		fn validate_transaction_parameters(block_number: &T::BlockNumber) -> TransactionValidity {
+			// Reject transactions sent before the chain accepts the next one.
+			let next_unsigned_at = <NextUnsignedAt<T, I>>::get();
+			if &next_unsigned_at > block_number {
+				return InvalidTransaction::Stale.into()
+			}
+			// Reject transactions computed in a block we have not reached yet.
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			if &current_block < block_number {
+				return InvalidTransaction::Future.into()
+			}
+
+			ValidTransaction::with_tag_prefix("TemplateOffchainWorker")
+				.priority(T::UnsignedPriority::get())
+				// Only one unsigned transaction is accepted per `UnsignedInterval`, so the next
+				// accepted block doubles as the tag. The pallet name keeps instances apart.
+				.and_provides((<Self as PalletInfoAccess>::name(), next_unsigned_at))
+				// The transaction is only valid for the next 5 blocks. After that it's
+				// going to be revalidated by the pool.
+				.longevity(5)
+				// It's fine to propagate that transaction to other peers, which means it can be
+				// created even by nodes that don't produce blocks.
+				.propagate(true)
+				.build()
+		}
+	}
+
+	/// The type of transaction an offchain worker sends in a given block.
+	enum TransactionType {
+		Signed,
+		UnsignedWithSignedPayload,
+		None,
 	}
 }
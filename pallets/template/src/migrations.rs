@@ -0,0 +1,110 @@
+//! Storage migrations for the template pallet.
+
+/// Migration from storage version 0 to 1.
+///
+/// Version 0 kept `Something` as a bare `u32`. Version 1 wraps it in a [`StoredValue`] that also
+/// records the block in which the value was last written. Values moved by this migration are
+/// stamped with the block in which the upgrade is applied.
+///
+/// Every instance of the pallet tracks its own storage version and is migrated separately.
+///
+/// [`StoredValue`]: crate::StoredValue
+pub mod v1 {
+	use crate::{Config, Pallet, Something, StoredValue};
+	use frame_support::{
+		traits::{Get, StorageVersion},
+		weights::Weight,
+	};
+
+	/// The version 0 layout, as read by the pre-upgrade checks.
+	#[cfg(feature = "try-runtime")]
+	mod v0 {
+		use crate::{Config, Pallet};
+		use frame_support::{
+			storage::types::{OptionQuery, StorageValue},
+			traits::{PalletInfoAccess, StorageInstance},
+		};
+		use sp_std::marker::PhantomData;
+
+		/// The prefix of `Something` in the storage of the given pallet instance.
+		///
+		/// `generate_storage_alias!` only takes a fixed pallet prefix, so it can't alias the
+		/// storage of each instance.
+		pub struct SomethingPrefix<T, I>(PhantomData<(T, I)>);
+
+		impl<T: Config<I>, I: 'static> StorageInstance for SomethingPrefix<T, I> {
+			fn pallet_prefix() -> &'static str {
+				<Pallet<T, I> as PalletInfoAccess>::name()
+			}
+			const STORAGE_PREFIX: &'static str = "Something";
+		}
+
+		/// `Something` as a bare `u32`.
+		pub type Something<T, I> = StorageValue<SomethingPrefix<T, I>, u32, OptionQuery>;
+	}
+
+	/// Move the `u32` stored in `Something` into the version 1 layout.
+	///
+	/// Does nothing if the on-chain storage version is already 1 or higher.
+	pub fn migrate<T: Config<I>, I: 'static>() -> Weight {
+		let on_chain_version = StorageVersion::get::<Pallet<T, I>>();
+		if on_chain_version >= StorageVersion::new(1) {
+			log::info!(
+				target: "runtime::template",
+				"Storage version {:?} is already up to date, skipping v1 migration",
+				on_chain_version,
+			);
+			return T::DbWeight::get().reads(1)
+		}
+
+		let updated_at = <frame_system::Pallet<T>>::block_number();
+		let translated = Something::<T, I>::translate::<u32, _>(|maybe_old| {
+			maybe_old.map(|value| StoredValue { value, updated_at })
+		});
+		if translated.is_err() {
+			log::error!(
+				target: "runtime::template",
+				"Failed to decode the version 0 value of `Something`",
+			);
+		}
+
+		StorageVersion::new(1).put::<Pallet<T, I>>();
+		log::info!(target: "runtime::template", "Migrated storage to version 1");
+
+		T::DbWeight::get().reads_writes(2, 2)
+	}
+
+	/// Check that the storage is in the version 0 layout before migrating.
+	///
+	/// Does nothing if the on-chain storage version is already 1 or higher, as the migration is
+	/// skipped in that case.
+	#[cfg(feature = "try-runtime")]
+	pub fn pre_migrate<T: Config<I>, I: 'static>() -> Result<(), &'static str> {
+		if StorageVersion::get::<Pallet<T, I>>() >= StorageVersion::new(1) {
+			return Ok(())
+		}
+		if v0::Something::<T, I>::exists() {
+			frame_support::ensure!(
+				v0::Something::<T, I>::get().is_some(),
+				"`Something` does not decode as a version 0 value",
+			);
+		}
+		Ok(())
+	}
+
+	/// Check that the storage is in the version 1 layout after migrating.
+	#[cfg(feature = "try-runtime")]
+	pub fn post_migrate<T: Config<I>, I: 'static>() -> Result<(), &'static str> {
+		frame_support::ensure!(
+			StorageVersion::get::<Pallet<T, I>>() == StorageVersion::new(1),
+			"the v1 migration did not bump the storage version",
+		);
+		if Something::<T, I>::exists() {
+			frame_support::ensure!(
+				Something::<T, I>::get().is_some(),
+				"`Something` does not decode as a version 1 value",
+			);
+		}
+		Ok(())
+	}
+}
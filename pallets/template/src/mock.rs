@@ -1,15 +1,20 @@
 use crate as pallet_template;
-use frame_support::parameter_types;
-use frame_system as system;
+use frame_support::{parameter_types, weights::IdentityFee};
+use frame_system::{self as system, EnsureRoot};
+use pallet_transaction_payment::CurrencyAdapter;
 use sp_core::H256;
 use sp_runtime::{
-	testing::Header,
+	testing::{Header, TestSignature, TestXt, UintAuthorityId},
 	traits::{BlakeTwo256, IdentityLookup},
+	transaction_validity::TransactionPriority,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
+/// The extrinsic type submitted to the transaction pool by the offchain worker.
+pub type Extrinsic = TestXt<Call, ()>;
+
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
 	pub enum Test where
@@ -18,7 +23,12 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
+		TemplateModule: pallet_template::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
+		SecondTemplateModule: pallet_template::<Instance1>::{
+			Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned
+		},
 	}
 );
 
@@ -45,7 +55,7 @@ impl system::Config for Test {
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -53,11 +63,153 @@ impl system::Config for Test {
 	type OnSetCode = ();
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+	pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const TransactionByteFee: u64 = 1;
+}
+
+impl pallet_transaction_payment::Config for Test {
+	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	type TransactionByteFee = TransactionByteFee;
+	type WeightToFee = IdentityFee<u64>;
+	type FeeMultiplierUpdate = ();
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		_public: UintAuthorityId,
+		_account: u64,
+		nonce: u64,
+	) -> Option<(Call, <Extrinsic as sp_runtime::traits::Extrinsic>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+/// Signs offchain worker payloads with the test keys set through `UintAuthorityId`.
+pub struct TestAuthId;
+
+impl frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
+parameter_types! {
+	pub const TemplateReserveId: [u8; 8] = *b"template";
+	pub const StorageDeposit: u64 = 10;
+	pub const UnsignedInterval: u64 = 4;
+	pub const UnsignedPriority: TransactionPriority = 1 << 20;
+	pub const ProcessingPeriod: u64 = 5;
+	pub const MaxQueueLen: u32 = 3;
+	pub const MaxPayloadLen: u32 = 16;
+	pub const PayloadByteDeposit: u64 = 1;
+	pub const RateLimitWindow: u64 = 10;
+	pub const MaxCallsPerWindow: u32 = 5;
+	pub const FreeQuotaEra: u64 = 20;
+	pub const FreeCallsPerEra: u32 = 2;
+	pub const MaxFreeCallLen: u32 = 10;
+	pub const MaxAuthorities: u32 = 2;
+}
+
 impl pallet_template::Config for Test {
 	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ReserveId = TemplateReserveId;
+	type StorageDeposit = StorageDeposit;
+	type AdminOrigin = EnsureRoot<u64>;
+	type AuthorityId = TestAuthId;
+	type MaxAuthorities = MaxAuthorities;
+	type UnsignedInterval = UnsignedInterval;
+	type UnsignedPriority = UnsignedPriority;
+	type ProcessingPeriod = ProcessingPeriod;
+	type MaxQueueLen = MaxQueueLen;
+	type MaxPayloadLen = MaxPayloadLen;
+	type PayloadByteDeposit = PayloadByteDeposit;
+	type RateLimitWindow = RateLimitWindow;
+	type MaxCallsPerWindow = MaxCallsPerWindow;
+	type FreeQuotaEra = FreeQuotaEra;
+	type FreeCallsPerEra = FreeCallsPerEra;
+	type MaxFreeCallLen = MaxFreeCallLen;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const SecondTemplateReserveId: [u8; 8] = *b"tmplsec2";
+	pub const SecondStorageDeposit: u64 = 20;
+}
+
+impl pallet_template::Config<pallet_template::Instance1> for Test {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ReserveId = SecondTemplateReserveId;
+	type StorageDeposit = SecondStorageDeposit;
+	type AdminOrigin = EnsureRoot<u64>;
+	type AuthorityId = TestAuthId;
+	type MaxAuthorities = MaxAuthorities;
+	type UnsignedInterval = UnsignedInterval;
+	type UnsignedPriority = UnsignedPriority;
+	type ProcessingPeriod = ProcessingPeriod;
+	type MaxQueueLen = MaxQueueLen;
+	type MaxPayloadLen = MaxPayloadLen;
+	type PayloadByteDeposit = PayloadByteDeposit;
+	type RateLimitWindow = RateLimitWindow;
+	type MaxCallsPerWindow = MaxCallsPerWindow;
+	type FreeQuotaEra = FreeQuotaEra;
+	type FreeCallsPerEra = FreeCallsPerEra;
+	type MaxFreeCallLen = MaxFreeCallLen;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 100), (2, 100), (3, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	// Account 1 runs the offchain worker of both instances.
+	pallet_template::GenesisConfig::<Test> { authorities: vec![1], ..Default::default() }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	pallet_template::GenesisConfig::<Test, pallet_template::Instance1> {
+		authorities: vec![1],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	t.into()
 }
@@ -1,5 +1,27 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	extensions::{FREE_CALL_NOT_ALLOWED, FREE_CALL_TOO_LARGE, FREE_QUOTA_EXHAUSTED, RATE_LIMITED},
+	mock::*,
+	migrations, CheckFreeQuota, CheckTemplateRateLimit, Entries, Error, NextUnsignedAt, Payloads,
+	Something, StoredPayload, StoredValue, TotalPayload, WeightInfo,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::WithPostDispatchInfo,
+	storage::unhashed,
+	traits::{GenesisBuild, Hooks, NamedReservableCurrency, OriginTrait, StorageVersion},
+	unsigned::ValidateUnsigned,
+	weights::{DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo},
+};
+use pallet_transaction_payment::ChargeTransactionPayment;
+use sp_core::offchain::{testing::TestTransactionPoolExt, TransactionPoolExt};
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	traits::{BlakeTwo256, Dispatchable, Hash, SignedExtension},
+	transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidityError},
+	DispatchError,
+};
+use std::convert::TryInto;
 
 #[test]
 fn it_works_for_default_value() {
@@ -15,6 +37,1141 @@ fn it_works_for_default_value() {
 fn correct_error_for_none_value() {
 	new_test_ext().execute_with(|| {
 		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		assert_noop!(
+			TemplateModule::cause_error(Origin::signed(1)),
+			Error::<Test>::NoneValue.with_weight(<() as WeightInfo>::cause_error_none_value())
+		);
+	});
+}
+
+#[test]
+fn set_entry_stores_value_per_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::set_entry(Origin::signed(2), 7));
+		assert_eq!(TemplateModule::entry(1), Some(42));
+		assert_eq!(TemplateModule::entry(2), Some(7));
+		// The global value is left untouched.
+		assert_eq!(TemplateModule::something(), None);
+	});
+}
+
+#[test]
+fn set_entry_overwrites_existing_value() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 43));
+		assert_eq!(TemplateModule::entry(1), Some(43));
+	});
+}
+
+#[test]
+fn increment_entry_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::increment_entry(Origin::signed(1)));
+		assert_eq!(TemplateModule::entry(1), Some(43));
+	});
+}
+
+#[test]
+fn increment_entry_fails_without_entry() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(TemplateModule::increment_entry(Origin::signed(1)), Error::<Test>::NoEntry);
+	});
+}
+
+#[test]
+fn increment_entry_fails_on_overflow() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), u32::MAX));
+		assert_noop!(
+			TemplateModule::increment_entry(Origin::signed(1)),
+			Error::<Test>::StorageOverflow
+		);
+	});
+}
+
+#[test]
+fn clear_entry_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::clear_entry(Origin::signed(1)));
+		assert_eq!(TemplateModule::entry(1), None);
+		assert_noop!(TemplateModule::clear_entry(Origin::signed(1)), Error::<Test>::NoEntry);
+	});
+}
+
+#[test]
+fn transfer_entry_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::transfer_entry(Origin::signed(1), 2));
+		assert_eq!(TemplateModule::entry(1), None);
+		assert_eq!(TemplateModule::entry(2), Some(42));
+	});
+}
+
+#[test]
+fn transfer_entry_fails_without_entry() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(TemplateModule::transfer_entry(Origin::signed(1), 2), Error::<Test>::NoEntry);
+	});
+}
+
+#[test]
+fn transfer_entry_fails_if_destination_owns_entry() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::set_entry(Origin::signed(2), 7));
+		assert_noop!(
+			TemplateModule::transfer_entry(Origin::signed(1), 2),
+			Error::<Test>::EntryExists
+		);
+	});
+}
+
+#[test]
+fn do_something_records_block_number() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_eq!(Something::<Test>::get(), Some(StoredValue { value: 42, updated_at: 5 }));
+	});
+}
+
+#[test]
+fn migrate_to_v1_wraps_existing_value() {
+	new_test_ext().execute_with(|| {
+		// Populate storage with the version 0 layout.
+		StorageVersion::new(0).put::<TemplateModule>();
+		unhashed::put(&Something::<Test>::hashed_key(), &42u32);
+		System::set_block_number(10);
+
+		migrations::v1::migrate::<Test, ()>();
+
+		assert_eq!(StorageVersion::get::<TemplateModule>(), StorageVersion::new(1));
+		assert_eq!(Something::<Test>::get(), Some(StoredValue { value: 42, updated_at: 10 }));
+		assert_eq!(TemplateModule::something(), Some(42));
+	});
+}
+
+#[test]
+fn migrate_to_v1_handles_empty_storage() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<TemplateModule>();
+
+		migrations::v1::migrate::<Test, ()>();
+
+		assert_eq!(StorageVersion::get::<TemplateModule>(), StorageVersion::new(1));
+		assert_eq!(Something::<Test>::get(), None);
+	});
+}
+
+#[test]
+fn migrate_to_v1_is_idempotent() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<TemplateModule>();
+		unhashed::put(&Something::<Test>::hashed_key(), &42u32);
+		System::set_block_number(10);
+		migrations::v1::migrate::<Test, ()>();
+
+		// Running the migration again must not touch the already migrated value.
+		System::set_block_number(20);
+		migrations::v1::migrate::<Test, ()>();
+
+		assert_eq!(Something::<Test>::get(), Some(StoredValue { value: 42, updated_at: 10 }));
+	});
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn migrate_to_v1_passes_upgrade_checks() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<TemplateModule>();
+		unhashed::put(&Something::<Test>::hashed_key(), &42u32);
+
+		assert_ok!(migrations::v1::pre_migrate::<Test, ()>());
+		migrations::v1::migrate::<Test, ()>();
+		assert_ok!(migrations::v1::post_migrate::<Test, ()>());
+	});
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn upgrade_checks_pass_on_migrated_storage() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<TemplateModule>();
+
+		assert_ok!(migrations::v1::pre_migrate::<Test, ()>());
+		migrations::v1::migrate::<Test, ()>();
+		assert_ok!(migrations::v1::post_migrate::<Test, ()>());
+	});
+}
+
+#[test]
+fn genesis_config_seeds_storage() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> {
+		something: Some(42),
+		entries: vec![(1, 7), (2, 8)],
+		authorities: vec![1],
+		phantom: Default::default(),
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		assert_eq!(Something::<Test>::get(), Some(StoredValue { value: 42, updated_at: 0 }));
+		assert_eq!(TemplateModule::entry(1), Some(7));
+		assert_eq!(TemplateModule::entry(2), Some(8));
+		assert_eq!(TemplateModule::entry(3), None);
+		assert_eq!(TemplateModule::authorities().into_inner(), vec![1]);
+	});
+}
+
+#[test]
+#[should_panic(expected = "Account owns more than one entry")]
+fn genesis_config_rejects_duplicate_owners() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> {
+		something: None,
+		entries: vec![(1, 7), (1, 8)],
+		authorities: Vec::new(),
+		phantom: Default::default(),
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+}
+
+#[test]
+fn submit_total_signed_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::submit_total_signed(Origin::signed(1), 42));
+		assert_eq!(TemplateModule::entry_total(), Some(42));
+	});
+}
+
+#[test]
+fn submit_total_signed_requires_authority() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::submit_total_signed(Origin::signed(2), 42),
+			Error::<Test>::NotAuthority
+		);
+		// The instances have separate authorities.
+		assert_ok!(SecondTemplateModule::set_authorities(Origin::root(), vec![2]));
+		assert_noop!(
+			TemplateModule::submit_total_signed(Origin::signed(2), 42),
+			Error::<Test>::NotAuthority
+		);
+		assert_ok!(SecondTemplateModule::submit_total_signed(Origin::signed(2), 42));
+	});
+}
+
+#[test]
+fn set_authorities_requires_admin_origin_and_is_bounded() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			TemplateModule::set_authorities(Origin::signed(1), vec![2]),
+			DispatchError::BadOrigin
+		);
+		let too_many = (0..=MaxAuthorities::get() as u64).collect::<Vec<_>>();
+		assert_noop!(
+			TemplateModule::set_authorities(Origin::root(), too_many),
+			Error::<Test>::TooManyAuthorities
+		);
+
+		assert_ok!(TemplateModule::set_authorities(Origin::root(), vec![2, 3]));
+		System::assert_last_event(Event::TemplateModule(crate::Event::AuthoritiesSet {
+			authorities: 2,
+		}));
+		assert!(!TemplateModule::is_authority(&1));
+		assert!(TemplateModule::is_authority(&3));
+	});
+}
+
+#[test]
+fn submit_total_unsigned_throttles_next_submission() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		let payload = TotalPayload { block_number: 3, total: 42, public: UintAuthorityId(1) };
+		let signature = TestSignature(1, payload.encode());
+
+		assert_ok!(TemplateModule::submit_total_unsigned_with_signed_payload(
+			Origin::none(),
+			payload,
+			signature
+		));
+		assert_eq!(TemplateModule::entry_total(), Some(42));
+		assert_eq!(TemplateModule::next_unsigned_at(), 3 + UnsignedInterval::get());
+	});
+}
+
+#[test]
+fn validate_unsigned_accepts_correctly_signed_payload() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		let payload = TotalPayload { block_number: 3, total: 42, public: UintAuthorityId(1) };
+		let signature = TestSignature(1, payload.encode());
+		let call =
+			crate::Call::<Test>::submit_total_unsigned_with_signed_payload(payload, signature);
+
+		let valid = TemplateModule::validate_unsigned(TransactionSource::External, &call).unwrap();
+		assert_eq!(valid.priority, UnsignedPriority::get());
+	});
+}
+
+#[test]
+fn validate_unsigned_rejects_bad_signature() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		let payload = TotalPayload { block_number: 3, total: 42, public: UintAuthorityId(1) };
+		// Signed by a different key than the one in the payload.
+		let signature = TestSignature(2, payload.encode());
+		let call =
+			crate::Call::<Test>::submit_total_unsigned_with_signed_payload(payload, signature);
+
+		assert_eq!(
+			TemplateModule::validate_unsigned(TransactionSource::External, &call),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::BadProof)),
+		);
+	});
+}
+
+#[test]
+fn validate_unsigned_rejects_unauthorized_key() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		// Correctly signed, but account 2 is not an authority.
+		let payload = TotalPayload { block_number: 3, total: 42, public: UintAuthorityId(2) };
+		let signature = TestSignature(2, payload.encode());
+		let call =
+			crate::Call::<Test>::submit_total_unsigned_with_signed_payload(payload, signature);
+
+		assert_eq!(
+			TemplateModule::validate_unsigned(TransactionSource::External, &call),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::BadSigner)),
+		);
+	});
+}
+
+#[test]
+fn validate_unsigned_rejects_stale_and_future_payloads() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		NextUnsignedAt::<Test>::put(3);
+
+		let stale = TotalPayload { block_number: 2, total: 42, public: UintAuthorityId(1) };
+		let signature = TestSignature(1, stale.encode());
+		let call = crate::Call::<Test>::submit_total_unsigned_with_signed_payload(stale, signature);
+		assert_eq!(
+			TemplateModule::validate_unsigned(TransactionSource::External, &call),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::Stale)),
+		);
+
+		let future = TotalPayload { block_number: 4, total: 42, public: UintAuthorityId(1) };
+		let signature = TestSignature(1, future.encode());
+		let call =
+			crate::Call::<Test>::submit_total_unsigned_with_signed_payload(future, signature);
+		assert_eq!(
+			TemplateModule::validate_unsigned(TransactionSource::External, &call),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::Future)),
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_submits_signed_transaction_on_even_blocks() {
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(TransactionPoolExt::new(pool));
+	UintAuthorityId::set_all_keys(vec![1u64]);
+
+	t.execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 40));
+		assert_ok!(TemplateModule::set_entry(Origin::signed(2), 2));
+
+		TemplateModule::offchain_worker(2);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, Some((0, ())));
+		assert_eq!(tx.call, Call::TemplateModule(crate::Call::submit_total_signed(42)));
+	});
+}
+
+#[test]
+fn offchain_worker_submits_unsigned_transaction_on_odd_blocks() {
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(TransactionPoolExt::new(pool));
+	UintAuthorityId::set_all_keys(vec![1u64]);
+
+	t.execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 42));
+
+		TemplateModule::offchain_worker(3);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, None);
+		let payload = TotalPayload { block_number: 3, total: 42, public: UintAuthorityId(1) };
+		let signature = TestSignature(1, payload.encode());
+		assert_eq!(
+			tx.call,
+			Call::TemplateModule(crate::Call::submit_total_unsigned_with_signed_payload(
+				payload, signature
+			))
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_skips_unchanged_total() {
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(TransactionPoolExt::new(pool));
+	UintAuthorityId::set_all_keys(vec![1u64]);
+
+	t.execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::submit_total_signed(Origin::signed(1), 42));
+
+		TemplateModule::offchain_worker(2);
+
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+fn reserved(who: u64) -> u64 {
+	Balances::reserved_balance_named(&TemplateReserveId::get(), &who)
+}
+
+#[test]
+fn set_entry_reserves_deposit_once() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 42));
+		assert_eq!(TemplateModule::deposit(1), Some(StorageDeposit::get()));
+		assert_eq!(reserved(1), StorageDeposit::get());
+		assert_eq!(Balances::free_balance(1), 100 - StorageDeposit::get());
+
+		// Overwriting the entry does not reserve a second deposit.
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 43));
+		assert_eq!(reserved(1), StorageDeposit::get());
+	});
+}
+
+#[test]
+fn set_entry_fails_without_funds_for_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::set_entry(Origin::signed(4), 42),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn clear_entry_refunds_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::clear_entry(Origin::signed(1)));
+		assert_eq!(TemplateModule::deposit(1), None);
+		assert_eq!(reserved(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+	});
+}
+
+#[test]
+fn clear_entry_without_deposit_works() {
+	new_test_ext().execute_with(|| {
+		// Entries created at genesis are not backed by a deposit.
+		Entries::<Test>::insert(1, 42);
+		assert_ok!(TemplateModule::clear_entry(Origin::signed(1)));
+		assert_eq!(Balances::free_balance(1), 100);
+	});
+}
+
+#[test]
+fn transfer_entry_moves_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::transfer_entry(Origin::signed(1), 2));
+
+		assert_eq!(TemplateModule::deposit(1), None);
+		assert_eq!(TemplateModule::deposit(2), Some(StorageDeposit::get()));
+		assert_eq!(reserved(1), 0);
+		assert_eq!(reserved(2), StorageDeposit::get());
+		assert_eq!(Balances::total_balance(&1), 100 - StorageDeposit::get());
+
+		// The new owner gets the deposit back when clearing the entry.
+		assert_ok!(TemplateModule::clear_entry(Origin::signed(2)));
+		assert_eq!(Balances::free_balance(2), 100 + StorageDeposit::get());
+	});
+}
+
+#[test]
+fn transfer_entry_to_unfunded_account_changes_nothing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 42));
+
+		// Account 4 doesn't exist, so it can't hold the deposit.
+		assert_noop!(
+			TemplateModule::transfer_entry(Origin::signed(1), 4),
+			pallet_balances::Error::<Test>::DeadAccount
+		);
+		assert_eq!(TemplateModule::entry(1), Some(42));
+		assert_eq!(TemplateModule::deposit(1), Some(StorageDeposit::get()));
+		assert_eq!(reserved(1), StorageDeposit::get());
+	});
+}
+
+#[test]
+fn force_set_requires_admin_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(TemplateModule::force_set(Origin::signed(1), 2, 42), DispatchError::BadOrigin);
+		assert_ok!(TemplateModule::force_set(Origin::root(), 2, 42));
+		assert_eq!(TemplateModule::entry(2), Some(42));
+		// No deposit is taken for entries set by the admin origin.
+		assert_eq!(TemplateModule::deposit(2), None);
+		assert_eq!(reserved(2), 0);
+	});
+}
+
+#[test]
+fn force_clear_refunds_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(TemplateModule::force_clear(Origin::root(), 1), Error::<Test>::NoEntry);
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 42));
+		assert_noop!(TemplateModule::force_clear(Origin::signed(2), 1), DispatchError::BadOrigin);
+
+		assert_ok!(TemplateModule::force_clear(Origin::root(), 1));
+		assert_eq!(TemplateModule::entry(1), None);
+		assert_eq!(TemplateModule::deposit(1), None);
+		assert_eq!(Balances::free_balance(1), 100);
+	});
+}
+
+#[test]
+fn kill_all_removes_up_to_limit_entries() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		for who in 1..=3 {
+			assert_ok!(TemplateModule::set_entry(Origin::signed(who), 42));
+			assert_ok!(TemplateModule::queue_entry_update(Origin::signed(who), 43));
+		}
+		assert_noop!(TemplateModule::kill_all(Origin::signed(1), 3), DispatchError::BadOrigin);
+
+		assert_ok!(TemplateModule::kill_all(Origin::root(), 2));
+		assert_eq!(TemplateModule::something(), None);
+		assert_eq!(Entries::<Test>::iter().count(), 1);
+		assert_eq!((1..=3).map(reserved).sum::<u64>(), StorageDeposit::get());
+		// The queue is cleared as well, so the next run doesn't touch the remaining entry.
+		assert!(TemplateModule::pending_updates().is_empty());
+		TemplateModule::on_initialize(ProcessingPeriod::get());
+		assert_eq!(Entries::<Test>::iter_values().collect::<Vec<_>>(), vec![42]);
+
+		assert_ok!(TemplateModule::kill_all(Origin::root(), 2));
+		assert_eq!(Entries::<Test>::iter().count(), 0);
+		assert_eq!((1..=3).map(reserved).sum::<u64>(), 0);
+	});
+}
+
+#[test]
+fn instances_have_separate_storage() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 7));
+		assert_eq!(SecondTemplateModule::something(), None);
+		assert_eq!(SecondTemplateModule::entry(1), None);
+
+		assert_ok!(SecondTemplateModule::set_entry(Origin::signed(1), 8));
+		assert_eq!(TemplateModule::entry(1), Some(7));
+		assert_eq!(SecondTemplateModule::entry(1), Some(8));
+
+		assert_ok!(TemplateModule::clear_entry(Origin::signed(1)));
+		assert_eq!(TemplateModule::entry(1), None);
+		assert_eq!(SecondTemplateModule::entry(1), Some(8));
+		assert_noop!(TemplateModule::increment_entry(Origin::signed(1)), Error::<Test>::NoEntry);
+	});
+}
+
+#[test]
+fn instances_hold_deposits_under_their_own_reserve_id() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 7));
+		assert_ok!(SecondTemplateModule::set_entry(Origin::signed(1), 8));
+		assert_eq!(reserved(1), StorageDeposit::get());
+		assert_eq!(
+			Balances::reserved_balance_named(&SecondTemplateReserveId::get(), &1),
+			SecondStorageDeposit::get()
+		);
+
+		// Killing one instance only refunds the deposits it holds.
+		assert_ok!(SecondTemplateModule::kill_all(Origin::root(), 10));
+		assert_eq!(SecondTemplateModule::entry(1), None);
+		assert_eq!(TemplateModule::entry(1), Some(7));
+		assert_eq!(reserved(1), StorageDeposit::get());
+		assert_eq!(Balances::free_balance(1), 100 - StorageDeposit::get());
+	});
+}
+
+#[test]
+fn instances_emit_their_own_events() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(SecondTemplateModule::set_entry(Origin::signed(1), 8));
+		System::assert_last_event(Event::SecondTemplateModule(
+			crate::Event::<Test, crate::Instance1>::EntrySet { who: 1, value: 8 },
+		));
+	});
+}
+
+#[test]
+fn queue_entry_update_requires_entry() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::queue_entry_update(Origin::signed(1), 42),
+			Error::<Test>::NoEntry
+		);
+	});
+}
+
+#[test]
+fn queue_entry_update_replaces_pending_update() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 42));
+		for value in 0..MaxQueueLen::get() + 1 {
+			assert_ok!(TemplateModule::queue_entry_update(Origin::signed(1), value));
+		}
+		assert_eq!(TemplateModule::pending_updates().into_inner(), vec![(1, MaxQueueLen::get())]);
+	});
+}
+
+#[test]
+fn queue_entry_update_is_bounded() {
+	new_test_ext().execute_with(|| {
+		for who in 1..=MaxQueueLen::get() as u64 + 1 {
+			Entries::<Test>::insert(who, 42);
+		}
+		for who in 1..=MaxQueueLen::get() as u64 {
+			assert_ok!(TemplateModule::queue_entry_update(Origin::signed(who), 7));
+		}
+		let next = MaxQueueLen::get() as u64 + 1;
+		assert_noop!(
+			TemplateModule::queue_entry_update(Origin::signed(next), 7),
+			Error::<Test>::QueueFull
+		);
+		// Accounts with a pending update can still replace it.
+		assert_ok!(TemplateModule::queue_entry_update(Origin::signed(1), 8));
+	});
+}
+
+#[test]
+fn queue_is_processed_every_period() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 1));
+		assert_ok!(TemplateModule::set_entry(Origin::signed(2), 2));
+		assert_ok!(TemplateModule::queue_entry_update(Origin::signed(1), 10));
+		assert_ok!(TemplateModule::queue_entry_update(Origin::signed(2), 20));
+		// The update of an entry removed in the meantime is skipped.
+		assert_ok!(TemplateModule::clear_entry(Origin::signed(2)));
+
+		assert_eq!(TemplateModule::on_initialize(ProcessingPeriod::get() - 1), 0);
+		assert_eq!(TemplateModule::entry(1), Some(1));
+		assert_eq!(TemplateModule::pending_updates().len(), 2);
+
+		assert_eq!(
+			TemplateModule::on_initialize(ProcessingPeriod::get()),
+			<() as crate::WeightInfo>::process_queue(2)
+		);
+		assert_eq!(TemplateModule::entry(1), Some(10));
+		assert_eq!(TemplateModule::entry(2), None);
+		assert!(TemplateModule::pending_updates().is_empty());
+		System::assert_last_event(Event::TemplateModule(crate::Event::QueueProcessed {
+			applied: 1,
+			skipped: 1,
+		}));
+	});
+}
+
+#[test]
+fn integrity_test_passes() {
+	new_test_ext().execute_with(|| {
+		<TemplateModule as Hooks<u64>>::integrity_test();
+		<SecondTemplateModule as Hooks<u64>>::integrity_test();
+	});
+}
+
+#[test]
+fn store_payload_keys_by_hash_and_reserves_deposit() {
+	new_test_ext().execute_with(|| {
+		let data = b"hello".to_vec();
+		let hash = BlakeTwo256::hash(&data);
+		assert_ok!(TemplateModule::store_payload(Origin::signed(1), data.clone()));
+
+		let deposit = StorageDeposit::get() + PayloadByteDeposit::get() * data.len() as u64;
+		assert_eq!(
+			TemplateModule::payload(hash),
+			Some(StoredPayload { owner: 1, deposit, data: data.clone().try_into().unwrap() })
+		);
+		assert_eq!(reserved(1), deposit);
+
+		// The same data cannot be stored twice, not even by another account.
+		assert_noop!(
+			TemplateModule::store_payload(Origin::signed(2), data),
+			Error::<Test>::PayloadExists
+		);
+	});
+}
+
+#[test]
+fn store_payload_rejects_oversized_data() {
+	new_test_ext().execute_with(|| {
+		let data = vec![0u8; MaxPayloadLen::get() as usize + 1];
+		assert_noop!(
+			TemplateModule::store_payload(Origin::signed(1), data),
+			Error::<Test>::PayloadTooLarge
+		);
+		assert_ok!(TemplateModule::store_payload(
+			Origin::signed(1),
+			vec![0u8; MaxPayloadLen::get() as usize]
+		));
+	});
+}
+
+#[test]
+fn remove_payload_refunds_owner() {
+	new_test_ext().execute_with(|| {
+		let data = b"hello".to_vec();
+		let hash = BlakeTwo256::hash(&data);
+		assert_noop!(
+			TemplateModule::remove_payload(Origin::signed(1), hash),
+			Error::<Test>::NoPayload
+		);
+		assert_ok!(TemplateModule::store_payload(Origin::signed(1), data));
+
+		assert_noop!(
+			TemplateModule::remove_payload(Origin::signed(2), hash),
+			Error::<Test>::NotPayloadOwner
+		);
+		assert_ok!(TemplateModule::remove_payload(Origin::signed(1), hash));
+		assert!(!Payloads::<Test>::contains_key(hash));
+		assert_eq!(reserved(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+	});
+}
+
+/// Dispatch `call` from `origin` and check that it is charged for exactly `actual_weight`.
+///
+/// Returns the post dispatch info, whether the call succeeded or not.
+fn assert_charged_for(call: Call, origin: Origin, actual_weight: u64) -> PostDispatchInfo {
+	let info = call.get_dispatch_info();
+	let len = call.encode().len() as u32;
+	let post_info = call.dispatch(origin).unwrap_or_else(|e| e.post_info);
+
+	assert_eq!(post_info.calc_actual_weight(&info), actual_weight);
+	assert_eq!(post_info.calc_unspent(&info), info.weight - actual_weight);
+	assert_eq!(
+		TransactionPayment::compute_actual_fee(len, &info, &post_info, 0),
+		TransactionPayment::compute_fee(len, &DispatchInfo { weight: actual_weight, ..info }, 0),
+	);
+	post_info
+}
+
+#[test]
+fn cause_error_refunds_weight_when_failing_early() {
+	new_test_ext().execute_with(|| {
+		let call = Call::TemplateModule(crate::Call::cause_error());
+		let declared = call.get_dispatch_info().weight;
+		let actual = <() as WeightInfo>::cause_error_none_value();
+		assert!(actual < declared);
+
+		assert_charged_for(call.clone(), Origin::signed(1), actual);
+
+		// The overflow error is refunded the same way.
+		Something::<Test>::put(StoredValue { value: u32::MAX, updated_at: 0 });
+		assert_charged_for(call, Origin::signed(1), actual);
+	});
+}
+
+#[test]
+fn cause_error_charges_full_weight_on_success() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		let call = Call::TemplateModule(crate::Call::cause_error());
+
+		let post_info =
+			assert_charged_for(call, Origin::signed(1), <() as WeightInfo>::cause_error());
+		assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::cause_error()));
+		assert_eq!(TemplateModule::something(), Some(43));
+	});
+}
+
+#[test]
+fn remove_payload_refunds_unused_length() {
+	new_test_ext().execute_with(|| {
+		let data = b"hello".to_vec();
+		let hash = BlakeTwo256::hash(&data);
+		assert_ok!(TemplateModule::store_payload(Origin::signed(1), data.clone()));
+
+		let call = Call::TemplateModule(crate::Call::remove_payload(hash));
+		assert_eq!(
+			call.get_dispatch_info().weight,
+			<() as WeightInfo>::remove_payload(MaxPayloadLen::get())
+		);
+		assert_charged_for(
+			call,
+			Origin::signed(1),
+			<() as WeightInfo>::remove_payload(data.len() as u32),
+		);
+	});
+}
+
+#[test]
+fn kill_all_charges_for_removed_entries_only() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::set_entry(Origin::signed(2), 42));
+
+		let call = Call::TemplateModule(crate::Call::kill_all(10));
+		assert_charged_for(call, Origin::root(), <() as WeightInfo>::kill_all(2));
+	});
+}
+
+#[test]
+fn events_are_indexed_by_account_and_value() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::set_entry(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::set_entry(Origin::signed(2), 7));
+		assert_ok!(TemplateModule::transfer_entry(Origin::signed(2), 3));
+		assert_ok!(TemplateModule::do_something(Origin::signed(3), 42));
+
+		let indices = |topic| -> Vec<u32> {
+			System::event_topics(topic).into_iter().map(|(_, index)| index).collect()
+		};
+		let template_events = |who| {
+			indices(TemplateModule::account_topic(&who))
+				.into_iter()
+				.map(|index| System::events()[index as usize].event.clone())
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(
+			template_events(3),
+			vec![
+				Event::TemplateModule(crate::Event::EntryTransferred { from: 2, to: 3 }),
+				Event::TemplateModule(crate::Event::SomethingStored { value: 42, who: 3 }),
+			]
+		);
+		assert_eq!(template_events(1).len(), 1);
+		assert_eq!(indices(TemplateModule::value_topic(&42u32)).len(), 2);
+		assert_eq!(indices(TemplateModule::value_topic(&7u32)).len(), 1);
+	});
+}
+
+#[test]
+fn event_topics_are_separate_per_instance() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(SecondTemplateModule::set_entry(Origin::signed(1), 42));
+
+		assert!(System::event_topics(TemplateModule::account_topic(&1)).is_empty());
+		assert_eq!(System::event_topics(SecondTemplateModule::account_topic(&1)).len(), 1);
+		assert!(System::event_topics(TemplateModule::value_topic(&42u32)).is_empty());
+	});
+}
+
+#[test]
+fn rate_limit_rejects_calls_over_the_limit_in_validate() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let call = Call::TemplateModule(crate::Call::do_something(42));
+		let info = call.get_dispatch_info();
+		let len = call.encode().len();
+		let ext = CheckTemplateRateLimit::<Test>::new();
+
+		for _ in 0..MaxCallsPerWindow::get() {
+			assert_ok!(ext.validate(&1, &call, &info, len));
+			assert_ok!(ext.clone().pre_dispatch(&1, &call, &info, len));
+			assert_ok!(call.clone().dispatch(Origin::signed(1)));
+		}
+		// The pool rejects the next call, so it never makes it into a block.
+		let limited = TransactionValidityError::from(InvalidTransaction::Custom(RATE_LIMITED));
+		assert_eq!(ext.validate(&1, &call, &info, len), Err(limited));
+		assert_eq!(ext.clone().pre_dispatch(&1, &call, &info, len), Err(limited));
+
+		// Other accounts are not affected.
+		assert_ok!(ext.validate(&2, &call, &info, len));
+		// The limit is lifted at the start of the next window.
+		System::set_block_number(RateLimitWindow::get());
+		assert_ok!(ext.validate(&1, &call, &info, len));
+		assert_ok!(call.dispatch(Origin::signed(1)));
+		assert_eq!(TemplateModule::rate_limit(1), (RateLimitWindow::get(), 1));
+	});
+}
+
+#[test]
+fn rate_limit_admits_one_call_per_account_into_the_pool() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let call = Call::TemplateModule(crate::Call::do_something(42));
+		let info = call.get_dispatch_info();
+		let len = call.encode().len();
+		let ext = CheckTemplateRateLimit::<Test>::new();
+		let provides = |who| ext.validate(&who, &call, &info, len).unwrap().provides;
+
+		// Two transactions of the same account compete for the same tag, so the pool only keeps
+		// one of them until it is included.
+		let first = provides(1);
+		assert_eq!(first.len(), 1);
+		assert_eq!(provides(1), first);
+		assert_ne!(provides(2), first);
+
+		assert_ok!(call.clone().dispatch(Origin::signed(1)));
+		assert_ne!(provides(1), first);
+
+		// The second instance and other pallets don't compete for the tag.
+		let second = Call::SecondTemplateModule(crate::Call::do_something(42));
+		let second_ext = CheckTemplateRateLimit::<Test, crate::Instance1>::new();
+		assert_ne!(second_ext.validate(&1, &second, &info, len).unwrap().provides, provides(1));
+		let remark = Call::System(frame_system::Call::remark(vec![]));
+		assert!(ext.validate(&1, &remark, &info, len).unwrap().provides.is_empty());
+	});
+}
+
+#[test]
+fn rate_limit_is_enforced_at_dispatch() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		give_entry(4);
+		for _ in 1..MaxCallsPerWindow::get() {
+			assert_ok!(TemplateModule::do_something(Origin::signed(4), 42));
+		}
+		// Calls wrapped in another call are counted as well.
+		assert_ok!(TemplateModule::free_call(
+			Origin::signed(4),
+			Box::new(crate::Call::do_something(43))
+		));
+		assert_eq!(TemplateModule::something(), Some(43));
+
+		assert_noop!(
+			TemplateModule::do_something(Origin::signed(4), 44),
+			Error::<Test>::RateLimited
+		);
+		assert_ok!(TemplateModule::free_call(
+			Origin::signed(4),
+			Box::new(crate::Call::do_something(44))
+		));
+		System::assert_last_event(Event::TemplateModule(crate::Event::FreeCallExecuted {
+			who: 4,
+			result: Err(Error::<Test>::RateLimited.into()),
+		}));
+		assert_eq!(TemplateModule::something(), Some(43));
+	});
+}
+
+#[test]
+fn rate_limit_is_separate_per_instance() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let call = Call::SecondTemplateModule(crate::Call::do_something(42));
+		let info = call.get_dispatch_info();
+		let len = call.encode().len();
+		let ext = CheckTemplateRateLimit::<Test>::new();
+		let second_ext = CheckTemplateRateLimit::<Test, crate::Instance1>::new();
+
+		for _ in 0..MaxCallsPerWindow::get() {
+			assert_ok!(call.clone().dispatch(Origin::signed(1)));
+		}
+		let limited = TransactionValidityError::from(InvalidTransaction::Custom(RATE_LIMITED));
+		assert_eq!(second_ext.validate(&1, &call, &info, len), Err(limited));
+		assert_noop!(
+			SecondTemplateModule::do_something(Origin::signed(1), 42),
+			Error::<Test, crate::Instance1>::RateLimited
+		);
+
+		// The default instance and other pallets are not affected.
+		assert_ok!(ext.validate(&1, &call, &info, len));
+		let remark = Call::System(frame_system::Call::remark(vec![]));
+		assert_ok!(second_ext.validate(&1, &remark, &remark.get_dispatch_info(), len));
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_eq!(TemplateModule::rate_limit(1), (0, 1));
+	});
+}
+
+fn free_call(inner: crate::Call<Test>) -> Call {
+	Call::TemplateModule(crate::Call::free_call(Box::new(inner)))
+}
+
+/// Give `who` an entry through the admin origin, which makes it eligible for free calls.
+fn give_entry(who: u64) {
+	assert_ok!(TemplateModule::force_set(Origin::root(), who, 0));
+}
+
+#[test]
+fn free_call_does_not_pay_fees_within_quota() {
+	new_test_ext().execute_with(|| {
+		// Account 4 holds no balance at all, but owns an entry.
+		give_entry(4);
+		let call = free_call(crate::Call::do_something(42));
+		let info = call.get_dispatch_info();
+		let len = call.encode().len();
+		assert_eq!(info.pays_fee, Pays::No);
+
+		assert_ok!(CheckFreeQuota::<Test>::new().validate(&4, &call, &info, len));
+		assert_ok!(ChargeTransactionPayment::<Test>::from(0).pre_dispatch(&4, &call, &info, len));
+		let post_info = call.dispatch(Origin::signed(4)).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+		assert_eq!(TemplateModule::something(), Some(42));
+		assert_eq!(TemplateModule::free_calls_left(&4), FreeCallsPerEra::get() - 1);
+
+		// The same call outside of the quota cannot pay its fee.
+		let paid = Call::TemplateModule(crate::Call::do_something(42));
+		let paid_info = paid.get_dispatch_info();
+		assert!(matches!(
+			ChargeTransactionPayment::<Test>::from(0).pre_dispatch(&4, &paid, &paid_info, len),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::Payment))
+		));
+	});
+}
+
+#[test]
+fn free_quota_is_checked_in_validate_and_refills_every_era() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		give_entry(4);
+		give_entry(5);
+		let call = free_call(crate::Call::do_something(42));
+		let info = call.get_dispatch_info();
+		let len = call.encode().len();
+		let ext = CheckFreeQuota::<Test>::new();
+
+		for _ in 0..FreeCallsPerEra::get() {
+			assert_ok!(ext.validate(&4, &call, &info, len));
+			assert_ok!(ext.clone().pre_dispatch(&4, &call, &info, len));
+			assert_ok!(call.clone().dispatch(Origin::signed(4)));
+		}
+		// The pool rejects the next free call, so it never makes it into a block.
+		let exhausted =
+			TransactionValidityError::from(InvalidTransaction::Custom(FREE_QUOTA_EXHAUSTED));
+		assert_eq!(ext.validate(&4, &call, &info, len), Err(exhausted));
+		assert_eq!(ext.clone().pre_dispatch(&4, &call, &info, len), Err(exhausted));
+		assert_noop!(
+			TemplateModule::free_call(Origin::signed(4), Box::new(crate::Call::do_something(42))),
+			Error::<Test>::FreeQuotaExhausted
+		);
+
+		// Other accounts have their own quota and other calls are not affected.
+		assert_ok!(ext.validate(&5, &call, &info, len));
+		let paid = Call::TemplateModule(crate::Call::do_something(42));
+		assert_ok!(ext.validate(&4, &paid, &paid.get_dispatch_info(), len));
+
+		System::set_block_number(FreeQuotaEra::get());
+		assert_eq!(TemplateModule::free_calls_left(&4), FreeCallsPerEra::get());
+		assert_ok!(ext.validate(&4, &call, &info, len));
+	});
+}
+
+#[test]
+fn free_call_reports_inner_result() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		give_entry(4);
+		// The inner call fails, but the free call itself succeeds and uses up the quota.
+		let inner = Box::new(crate::Call::cause_error());
+		assert_ok!(TemplateModule::free_call(Origin::signed(4), inner));
+		System::assert_last_event(Event::TemplateModule(crate::Event::FreeCallExecuted {
+			who: 4,
+			result: Err(Error::<Test>::NoneValue.into()),
+		}));
+		assert_eq!(TemplateModule::free_calls_left(&4), FreeCallsPerEra::get() - 1);
+	});
+}
+
+#[test]
+fn free_call_respects_origin_filters() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		give_entry(4);
+		let mut origin = Origin::signed(4);
+		origin.add_filter(|call| {
+			!matches!(call, Call::TemplateModule(crate::Call::do_something(..)))
+		});
+
+		assert_ok!(TemplateModule::free_call(origin, Box::new(crate::Call::do_something(42))));
+		assert_eq!(TemplateModule::something(), None);
+	});
+}
+
+#[test]
+fn free_call_cannot_be_nested() {
+	new_test_ext().execute_with(|| {
+		let nested = crate::Call::free_call(Box::new(crate::Call::do_something(42)));
+		let call = free_call(nested.clone());
+		let info = call.get_dispatch_info();
+		let len = call.encode().len();
+
+		assert_eq!(
+			CheckFreeQuota::<Test>::new().validate(&4, &call, &info, len),
+			Err(InvalidTransaction::Call.into())
+		);
+		assert_noop!(
+			TemplateModule::free_call(Origin::signed(4), Box::new(nested)),
+			Error::<Test>::NestedFreeCall
+		);
+	});
+}
+
+#[test]
+fn free_call_requires_an_entry() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let call = free_call(crate::Call::do_something(42));
+		let info = call.get_dispatch_info();
+		let len = call.encode().len();
+		let ext = CheckFreeQuota::<Test>::new();
+
+		let not_allowed =
+			TransactionValidityError::from(InvalidTransaction::Custom(FREE_CALL_NOT_ALLOWED));
+		assert_eq!(ext.validate(&4, &call, &info, len), Err(not_allowed));
+		assert_noop!(
+			TemplateModule::free_call(Origin::signed(4), Box::new(crate::Call::do_something(42))),
+			Error::<Test>::NoEntry
+		);
+
+		give_entry(4);
+		assert_ok!(ext.validate(&4, &call, &info, len));
+		assert_ok!(call.dispatch(Origin::signed(4)));
+		assert_eq!(TemplateModule::something(), Some(42));
+	});
+}
+
+#[test]
+fn oversized_call_is_not_free() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		give_entry(4);
+		let data = vec![1u8; MaxFreeCallLen::get() as usize];
+		let inner = crate::Call::store_payload(data);
+		assert!(inner.encoded_size() > MaxFreeCallLen::get() as usize);
+		let call = free_call(inner.clone());
+		let info = call.get_dispatch_info();
+		let len = call.encode().len();
+
+		let too_large =
+			TransactionValidityError::from(InvalidTransaction::Custom(FREE_CALL_TOO_LARGE));
+		assert_eq!(CheckFreeQuota::<Test>::new().validate(&4, &call, &info, len), Err(too_large));
+		// Wrapped in another call, it fails at dispatch without using up the quota.
+		assert_noop!(
+			TemplateModule::free_call(Origin::signed(4), Box::new(inner)),
+			Error::<Test>::FreeCallTooLarge
+		);
+		assert_eq!(TemplateModule::free_calls_left(&4), FreeCallsPerEra::get());
 	});
 }
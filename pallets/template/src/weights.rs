@@ -0,0 +1,249 @@
+//! Weights for pallet_template
+//!
+//! These weights are estimated by hand from the storage accesses of each call and have not
+//! been generated by the benchmark CLI. Regenerate them on reference hardware with the
+//! command below, which overwrites this file, before relying on them in production.
+
+// Command to generate the weights:
+// ./target/release/node-template
+// benchmark
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet=pallet_template
+// --extrinsic=*
+// --steps=50
+// --repeat=20
+// --output=./pallets/template/src/weights.rs
+// --template=./.maintain/frame-weight-template.hbs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_template.
+pub trait WeightInfo {
+	fn do_something() -> Weight;
+	fn cause_error() -> Weight;
+	fn cause_error_none_value() -> Weight;
+	fn set_entry() -> Weight;
+	fn increment_entry() -> Weight;
+	fn clear_entry() -> Weight;
+	fn transfer_entry() -> Weight;
+	fn submit_total_signed() -> Weight;
+	fn submit_total_unsigned_with_signed_payload() -> Weight;
+	fn force_set() -> Weight;
+	fn force_clear() -> Weight;
+	fn kill_all(n: u32, ) -> Weight;
+	fn set_authorities(n: u32, ) -> Weight;
+	fn queue_entry_update() -> Weight;
+	fn process_queue(n: u32, ) -> Weight;
+	fn store_payload(l: u32, ) -> Weight;
+	fn remove_payload(l: u32, ) -> Weight;
+	fn free_call() -> Weight;
+}
+
+/// Hand-estimated weights for pallet_template, see the module documentation.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn do_something() -> Weight {
+		(17_654_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn cause_error() -> Weight {
+		(21_370_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn cause_error_none_value() -> Weight {
+		(7_912_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_entry() -> Weight {
+		(41_562_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	fn increment_entry() -> Weight {
+		(22_486_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn clear_entry() -> Weight {
+		(39_907_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn transfer_entry() -> Weight {
+		(68_431_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	fn submit_total_signed() -> Weight {
+		(16_297_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn submit_total_unsigned_with_signed_payload() -> Weight {
+		(19_748_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn force_set() -> Weight {
+		(15_936_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn force_clear() -> Weight {
+		(38_274_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn kill_all(n: u32, ) -> Weight {
+		(9_803_000 as Weight)
+			.saturating_add((33_518_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	fn set_authorities(n: u32, ) -> Weight {
+		(9_614_000 as Weight)
+			.saturating_add((102_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn queue_entry_update() -> Weight {
+		(24_913_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn process_queue(n: u32, ) -> Weight {
+		(6_237_000 as Weight)
+			.saturating_add((11_482_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn store_payload(l: u32, ) -> Weight {
+		(44_817_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn remove_payload(l: u32, ) -> Weight {
+		(39_552_000 as Weight)
+			.saturating_add((1_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn free_call() -> Weight {
+		(9_872_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn do_something() -> Weight {
+		(17_654_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn cause_error() -> Weight {
+		(21_370_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn cause_error_none_value() -> Weight {
+		(7_912_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_entry() -> Weight {
+		(41_562_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn increment_entry() -> Weight {
+		(22_486_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn clear_entry() -> Weight {
+		(39_907_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn transfer_entry() -> Weight {
+		(68_431_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+	}
+	fn submit_total_signed() -> Weight {
+		(16_297_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn submit_total_unsigned_with_signed_payload() -> Weight {
+		(19_748_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn force_set() -> Weight {
+		(15_936_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn force_clear() -> Weight {
+		(38_274_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn kill_all(n: u32, ) -> Weight {
+		(9_803_000 as Weight)
+			.saturating_add((33_518_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	fn set_authorities(n: u32, ) -> Weight {
+		(9_614_000 as Weight)
+			.saturating_add((102_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn queue_entry_update() -> Weight {
+		(24_913_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn process_queue(n: u32, ) -> Weight {
+		(6_237_000 as Weight)
+			.saturating_add((11_482_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn store_payload(l: u32, ) -> Weight {
+		(44_817_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn remove_payload(l: u32, ) -> Weight {
+		(39_552_000 as Weight)
+			.saturating_add((1_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn free_call() -> Weight {
+		(9_872_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
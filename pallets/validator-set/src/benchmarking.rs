@@ -0,0 +1,57 @@
+//! Benchmarking setup for pallet-validator-set
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as ValidatorSet;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_support::{
+	traits::{EnsureOrigin, Get},
+	BoundedVec,
+};
+use sp_runtime::traits::Convert;
+use sp_std::convert::TryFrom;
+
+/// Fill the validator set with `n` benchmark accounts.
+fn set_validators<T: Config>(n: u32) {
+	let validators = (0..n).map(|i| account("validator", i, 0)).collect::<Vec<T::AccountId>>();
+	let validators = BoundedVec::try_from(validators).expect("n is at most MaxValidators; qed");
+	Validators::<T>::put(validators);
+}
+
+benchmarks! {
+	add_validator {
+		set_validators::<T>(T::MaxValidators::get() - 1);
+		let validator: T::AccountId = account("new", 0, 0);
+		let origin = T::AddRemoveOrigin::successful_origin();
+	}: _<T::Origin>(origin, validator.clone())
+	verify {
+		assert!(ValidatorSet::<T>::validators().contains(&validator));
+	}
+
+	remove_validator {
+		set_validators::<T>(T::MaxValidators::get());
+		let validator: T::AccountId = account("validator", 0, 0);
+		let origin = T::AddRemoveOrigin::successful_origin();
+	}: _<T::Origin>(origin, validator.clone())
+	verify {
+		assert!(!ValidatorSet::<T>::validators().contains(&validator));
+	}
+
+	disable_validator {
+		set_validators::<T>(T::MaxValidators::get());
+		let validators = ValidatorSet::<T>::validators().into_inner();
+		let active = validators
+			.iter()
+			.filter_map(|v| T::ValidatorIdOf::convert(v.clone()))
+			.collect::<Vec<_>>();
+		pallet_session::Validators::<T>::put(active);
+		let validator = validators[0].clone();
+		let origin = T::AddRemoveOrigin::successful_origin();
+	}: _<T::Origin>(origin, validator)
+	verify {
+		assert!(pallet_session::Pallet::<T>::disabled_validators().contains(&0));
+	}
+}
+
+impl_benchmark_test_suite!(ValidatorSet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,338 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Validator Set Pallet
+//!
+//! Keeps the set of validators that `pallet_session` rotates in, and lets `AddRemoveOrigin` add
+//! and remove validators without a runtime upgrade.
+//!
+//! Changes to the set are picked up by the session pallet at the next session boundary through
+//! the [`pallet_session::SessionManager`] implementation of this pallet. As with any new session
+//! set, they only become active one session after that, once the queued keys are applied.
+//!
+//! Validators reported for an offence through `pallet_offences` are removed from the set in the
+//! same way, unless that would leave fewer than `MinValidators`.
+//!
+//! Removing a validator only takes effect two sessions later. In the meantime a misbehaving
+//! validator can be disabled for the rest of the current session, either by `AddRemoveOrigin` or
+//! by an offence report. The session pallet keeps track of disabled validators, and Aura rejects
+//! blocks authored by them as long as `pallet_aura::Config::DisabledValidators` is the session
+//! pallet. All validators are enabled again when the next session starts. At most
+//! `DisabledValidatorsThreshold` of the active validators, rounded down but at least one, can be
+//! disabled at the same time.
+//!
+//! A validator has to register its session keys through `Session::set_keys` before it is added.
+//! The session pallet leaves validators without keys out of the queued set, and they are only
+//! reconsidered the next time the set changes.
+//!
+//! The initial set comes from the genesis config. A chain that adds this pallet in a runtime
+//! upgrade has to add its current authorities and their keys in a migration, or its first
+//! sessions have no validators to hand to the session pallet.
+//!
+//! The historical session pallet notes the validators of every session, so that key ownership
+//! can be proven for offences in past sessions. Whenever a session starts, the sessions more than
+//! `HistoricalDepth` before it are pruned from its storage.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+use frame_support::{ensure, traits::Get, weights::Weight};
+use sp_runtime::{traits::Convert, DispatchResult, Perbill};
+use sp_staking::{
+	offence::{OffenceDetails, OnOffenceHandler},
+	SessionIndex,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::WeightInfo;
+	use frame_support::pallet_prelude::*;
+	use sp_staking::SessionIndex;
+	use frame_system::pallet_prelude::*;
+	use sp_std::{convert::TryFrom, prelude::*};
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config + pallet_session::Config + pallet_session::historical::Config
+	{
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The origin that can add and remove validators.
+		type AddRemoveOrigin: EnsureOrigin<Self::Origin>;
+		/// The smallest number of validators the set can be reduced to.
+		#[pallet::constant]
+		type MinValidators: Get<u32>;
+		/// The largest number of validators the set can hold.
+		#[pallet::constant]
+		type MaxValidators: Get<u32>;
+		/// The number of past sessions kept by the historical session pallet. It should cover
+		/// the longest time for which offences in a session can still be reported.
+		#[pallet::constant]
+		type HistoricalDepth: Get<SessionIndex>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// The validator set handed to the session pallet at the next session boundary.
+	#[pallet::storage]
+	#[pallet::getter(fn validators)]
+	pub type Validators<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxValidators>, ValueQuery>;
+
+	/// Whether `Validators` changed since it was last handed to the session pallet.
+	#[pallet::storage]
+	pub(super) type ValidatorsChanged<T> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The validators of the first sessions.
+		pub initial_validators: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { initial_validators: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let mut validators = Vec::with_capacity(self.initial_validators.len());
+			for who in &self.initial_validators {
+				assert!(!validators.contains(who), "Validator is listed more than once");
+				validators.push(who.clone());
+			}
+			let validators = BoundedVec::<_, T::MaxValidators>::try_from(validators)
+				.expect("More initial validators than MaxValidators");
+			<Validators<T>>::put(validators);
+			// Hand the initial set to the session pallet when it builds its own genesis.
+			<ValidatorsChanged<T>>::put(true);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId")]
+	pub enum Event<T: Config> {
+		/// A validator was added to the set. It takes part in consensus from the session after
+		/// the next one.
+		ValidatorAdded { validator: T::AccountId },
+		/// A validator was removed from the set. It stops taking part in consensus from the
+		/// session after the next one.
+		ValidatorRemoved { validator: T::AccountId },
+		/// A validator was removed from the set after being reported for an offence. It stops
+		/// taking part in consensus from the session after the next one.
+		OffenderRemoved { validator: T::AccountId },
+		/// A validator was disabled for the rest of the current session.
+		ValidatorDisabled { validator: T::AccountId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is already in the validator set.
+		AlreadyValidator,
+		/// The account is not in the validator set, or not active in the current session.
+		NotValidator,
+		/// The validator set is already at `MaxValidators`.
+		TooManyValidators,
+		/// Removing the validator would leave fewer than `MinValidators`.
+		TooFewValidators,
+		/// The validator is already disabled in the current session.
+		AlreadyDisabled,
+		/// Disabling the validator would exceed `DisabledValidatorsThreshold`.
+		TooManyDisabled,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			assert!(T::MinValidators::get() > 0, "MinValidators must be greater than zero");
+			assert!(
+				T::MinValidators::get() <= T::MaxValidators::get(),
+				"MinValidators must not exceed MaxValidators",
+			);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Add `validator` to the validator set.
+		///
+		/// The change is applied at the next session boundary.
+		///
+		/// The dispatch origin for this call must be `AddRemoveOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::add_validator())]
+		pub fn add_validator(origin: OriginFor<T>, validator: T::AccountId) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			<Validators<T>>::try_mutate(|validators| -> DispatchResult {
+				ensure!(!validators.contains(&validator), Error::<T>::AlreadyValidator);
+				validators.try_push(validator.clone()).map_err(|_| Error::<T>::TooManyValidators)?;
+				Ok(())
+			})?;
+			<ValidatorsChanged<T>>::put(true);
+
+			Self::deposit_event(Event::ValidatorAdded { validator });
+			Ok(())
+		}
+
+		/// Remove `validator` from the validator set.
+		///
+		/// The change is applied at the next session boundary.
+		///
+		/// The dispatch origin for this call must be `AddRemoveOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_validator())]
+		pub fn remove_validator(origin: OriginFor<T>, validator: T::AccountId) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			Self::do_remove_validator(&validator)?;
+
+			Self::deposit_event(Event::ValidatorRemoved { validator });
+			Ok(())
+		}
+
+		/// Disable `validator` for the rest of the current session.
+		///
+		/// The validator stays in the validator set and is enabled again when the next session
+		/// starts.
+		///
+		/// The dispatch origin for this call must be `AddRemoveOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::disable_validator())]
+		pub fn disable_validator(origin: OriginFor<T>, validator: T::AccountId) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			Self::do_disable_validator(&validator)?;
+
+			Self::deposit_event(Event::ValidatorDisabled { validator });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Remove `validator` from the set, keeping at least `MinValidators`.
+	fn do_remove_validator(validator: &T::AccountId) -> DispatchResult {
+		<Validators<T>>::try_mutate(|validators| -> DispatchResult {
+			let index =
+				validators.iter().position(|v| v == validator).ok_or(Error::<T>::NotValidator)?;
+			ensure!(
+				validators.len() as u32 > T::MinValidators::get(),
+				Error::<T>::TooFewValidators
+			);
+			validators.remove(index);
+			Ok(())
+		})?;
+		<ValidatorsChanged<T>>::put(true);
+		Ok(())
+	}
+
+	/// Disable `validator` in the current session, keeping within `DisabledValidatorsThreshold`.
+	///
+	/// One validator can always be disabled, even if the threshold rounds down to zero.
+	fn do_disable_validator(validator: &T::AccountId) -> DispatchResult {
+		let id = T::ValidatorIdOf::convert(validator.clone()).ok_or(Error::<T>::NotValidator)?;
+		let active = <pallet_session::Pallet<T>>::validators();
+		let index = active.iter().position(|v| *v == id).ok_or(Error::<T>::NotValidator)?;
+
+		let disabled = <pallet_session::Pallet<T>>::disabled_validators();
+		ensure!(!disabled.contains(&(index as u32)), Error::<T>::AlreadyDisabled);
+		// Round the threshold down, but always allow one validator to be disabled so that offences
+		// still take effect in small sets.
+		let max_disabled =
+			T::DisabledValidatorsThreshold::get().mul_floor(active.len() as u32).max(1);
+		ensure!((disabled.len() as u32) < max_disabled, Error::<T>::TooManyDisabled);
+
+		<pallet_session::Pallet<T>>::disable_index(index);
+		Ok(())
+	}
+}
+
+impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
+	fn new_session(_new_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+		if !<ValidatorsChanged<T>>::take() {
+			return None
+		}
+		Some(Self::validators().into_inner())
+	}
+
+	fn end_session(_end_index: SessionIndex) {}
+
+	fn start_session(start_index: SessionIndex) {
+		if let Some(up_to) = start_index.checked_sub(T::HistoricalDepth::get()) {
+			<pallet_session::historical::Pallet<T>>::prune_up_to(up_to);
+		}
+	}
+}
+
+/// Identify validators by their account in the historical session pallet, so that key ownership
+/// proofs resolve to `(account, account)`.
+impl<T: Config> pallet_session::historical::SessionManager<T::AccountId, T::AccountId>
+	for Pallet<T>
+{
+	fn new_session(new_index: SessionIndex) -> Option<Vec<(T::AccountId, T::AccountId)>> {
+		<Self as pallet_session::SessionManager<_>>::new_session(new_index)
+			.map(|validators| validators.into_iter().map(|v| (v.clone(), v)).collect())
+	}
+
+	fn end_session(end_index: SessionIndex) {
+		<Self as pallet_session::SessionManager<_>>::end_session(end_index)
+	}
+
+	fn start_session(start_index: SessionIndex) {
+		<Self as pallet_session::SessionManager<_>>::start_session(start_index)
+	}
+}
+
+/// Disable reported offenders for the rest of the session and remove them from the validator set.
+///
+/// Each step is skipped when it isn't possible, for instance when the offender is no longer a
+/// validator or `MinValidators` would not be kept; the offence itself is still recorded by
+/// `pallet_offences`.
+impl<T: Config, FullIdentification>
+	OnOffenceHandler<T::AccountId, (T::AccountId, FullIdentification), Weight> for Pallet<T>
+{
+	fn on_offence(
+		offenders: &[OffenceDetails<T::AccountId, (T::AccountId, FullIdentification)>],
+		_slash_fraction: &[Perbill],
+		_session: SessionIndex,
+	) -> Weight {
+		for details in offenders {
+			let (validator, _) = &details.offender;
+			if Self::do_disable_validator(validator).is_ok() {
+				Self::deposit_event(Event::ValidatorDisabled { validator: validator.clone() });
+			}
+			if Self::do_remove_validator(validator).is_ok() {
+				Self::deposit_event(Event::OffenderRemoved { validator: validator.clone() });
+			}
+		}
+		<T as Config>::WeightInfo::disable_validator()
+			.saturating_add(T::DbWeight::get().reads_writes(1, 2))
+			.saturating_mul(offenders.len() as Weight)
+	}
+}
+
+/// Use the account of a validator as its validator id in the session pallet.
+pub struct ValidatorOf<T>(PhantomData<T>);
+
+impl<T: Config> Convert<T::AccountId, Option<T::AccountId>> for ValidatorOf<T> {
+	fn convert(account: T::AccountId) -> Option<T::AccountId> {
+		Some(account)
+	}
+}
@@ -0,0 +1,160 @@
+use crate as pallet_validator_set;
+use frame_support::{
+	parameter_types,
+	traits::{GenesisBuild, Get},
+};
+use frame_system::{self as system, EnsureRoot};
+use pallet_session::historical as pallet_session_historical;
+use sp_core::{crypto::KeyTypeId, H256};
+use sp_runtime::{
+	testing::{Header, UintAuthorityId},
+	traits::{BlakeTwo256, IdentityLookup, OpaqueKeys},
+	Perbill, RuntimeAppPublic,
+};
+use sp_std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		ValidatorSet: pallet_validator_set::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Session: pallet_session::{Pallet, Call, Storage, Config<T>, Event},
+		Historical: pallet_session_historical::{Pallet},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const MinValidators: u32 = 2;
+	pub const MaxValidators: u32 = 4;
+	pub const HistoricalDepth: u32 = 2;
+}
+
+impl pallet_validator_set::Config for Test {
+	type Event = Event;
+	type AddRemoveOrigin = EnsureRoot<u64>;
+	type MinValidators = MinValidators;
+	type MaxValidators = MaxValidators;
+	type HistoricalDepth = HistoricalDepth;
+	type WeightInfo = ();
+}
+
+/// Accepts any session keys and ignores session changes.
+pub struct TestSessionHandler;
+
+impl pallet_session::SessionHandler<u64> for TestSessionHandler {
+	const KEY_TYPE_IDS: &'static [KeyTypeId] = &[UintAuthorityId::ID];
+	fn on_genesis_session<Ks: OpaqueKeys>(_validators: &[(u64, Ks)]) {}
+	fn on_new_session<Ks: OpaqueKeys>(
+		_changed: bool,
+		_validators: &[(u64, Ks)],
+		_queued_validators: &[(u64, Ks)],
+	) {
+	}
+	fn on_disabled(_validator_index: usize) {}
+}
+
+parameter_types! {
+	pub const Period: u64 = 5;
+	pub const Offset: u64 = 0;
+}
+
+thread_local! {
+	static DISABLED_VALIDATORS_THRESHOLD: RefCell<Perbill> =
+		RefCell::new(Perbill::from_percent(50));
+}
+
+/// The share of validators that can be disabled at the same time, configurable per test.
+pub struct DisabledValidatorsThreshold;
+
+impl DisabledValidatorsThreshold {
+	pub fn set(threshold: Perbill) {
+		DISABLED_VALIDATORS_THRESHOLD.with(|v| *v.borrow_mut() = threshold);
+	}
+}
+
+impl Get<Perbill> for DisabledValidatorsThreshold {
+	fn get() -> Perbill {
+		DISABLED_VALIDATORS_THRESHOLD.with(|v| *v.borrow())
+	}
+}
+
+impl pallet_session::Config for Test {
+	type Event = Event;
+	type ValidatorId = u64;
+	type ValidatorIdOf = pallet_validator_set::ValidatorOf<Test>;
+	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
+	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
+	type SessionManager = pallet_session_historical::NoteHistoricalRoot<Test, ValidatorSet>;
+	type SessionHandler = TestSessionHandler;
+	type Keys = UintAuthorityId;
+	type DisabledValidatorsThreshold = DisabledValidatorsThreshold;
+	type WeightInfo = ();
+}
+
+impl pallet_session_historical::Config for Test {
+	type FullIdentification = u64;
+	type FullIdentificationOf = pallet_validator_set::ValidatorOf<Test>;
+}
+
+/// Move to the next block until session `index` has started.
+pub fn start_session(index: u32) {
+	use frame_support::traits::OnInitialize;
+	while Session::current_index() < index {
+		let now = System::block_number() + 1;
+		System::set_block_number(now);
+		Session::on_initialize(now);
+	}
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let validators = vec![1, 2, 3];
+	pallet_validator_set::GenesisConfig::<Test> { initial_validators: validators.clone() }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	pallet_session::GenesisConfig::<Test> {
+		keys: validators.into_iter().map(|v| (v, v, UintAuthorityId(v))).collect(),
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	t.into()
+}
@@ -0,0 +1,261 @@
+use crate::{mock::*, Error, Event as ValidatorSetEvent};
+use frame_support::{assert_noop, assert_ok, traits::DisabledValidators, weights::Weight};
+use pallet_session::SessionManager;
+use sp_runtime::{testing::UintAuthorityId, DispatchError, Perbill};
+use sp_staking::offence::{OffenceDetails, OnOffenceHandler};
+
+/// Create account `who` and register its session keys.
+fn register_keys(who: u64) {
+	System::inc_providers(&who);
+	assert_ok!(Session::set_keys(Origin::signed(who), UintAuthorityId(who), vec![]));
+}
+
+#[test]
+fn genesis_validators_are_active() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2, 3]);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+		// The genesis set has already been handed to the session pallet.
+		assert_eq!(ValidatorSet::new_session(1), None);
+	});
+}
+
+#[test]
+fn only_root_can_change_validators() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ValidatorSet::add_validator(Origin::signed(1), 4), DispatchError::BadOrigin);
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::signed(1), 3),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn added_validator_joins_after_the_next_session() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		register_keys(4);
+
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		System::assert_last_event(Event::ValidatorSet(ValidatorSetEvent::ValidatorAdded {
+			validator: 4,
+		}));
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2, 3, 4]);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+
+		// The new set is queued at the next session boundary ...
+		start_session(1);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+		assert_eq!(Session::queued_keys().len(), 4);
+
+		// ... and becomes active one session later.
+		start_session(2);
+		assert_eq!(Session::validators(), vec![1, 2, 3, 4]);
+	});
+}
+
+#[test]
+fn removed_validator_leaves_after_the_next_session() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 2));
+		System::assert_last_event(Event::ValidatorSet(ValidatorSetEvent::ValidatorRemoved {
+			validator: 2,
+		}));
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 3]);
+
+		start_session(1);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+		start_session(2);
+		assert_eq!(Session::validators(), vec![1, 3]);
+	});
+}
+
+#[test]
+fn validator_without_keys_is_not_queued() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+
+		start_session(2);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+	});
+}
+
+#[test]
+fn session_manager_only_reports_changed_sets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert_eq!(ValidatorSet::new_session(1), Some(vec![1, 2, 3, 4]));
+		assert_eq!(ValidatorSet::new_session(2), None);
+	});
+}
+
+#[test]
+fn add_validator_checks_the_set() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 1),
+			Error::<Test>::AlreadyValidator
+		);
+
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 5),
+			Error::<Test>::TooManyValidators
+		);
+	});
+}
+
+#[test]
+fn remove_validator_checks_the_set() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 4),
+			Error::<Test>::NotValidator
+		);
+
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 3));
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 2),
+			Error::<Test>::TooFewValidators
+		);
+	});
+}
+
+/// Report an offence committed by each of `offenders`.
+fn report(offenders: &[u64]) {
+	let details = offenders
+		.iter()
+		.map(|&v| OffenceDetails { offender: (v, v), reporters: vec![] })
+		.collect::<Vec<_>>();
+	let slash_fraction = vec![Perbill::zero(); offenders.len()];
+	<ValidatorSet as OnOffenceHandler<u64, (u64, u64), Weight>>::on_offence(
+		&details,
+		&slash_fraction,
+		Session::current_index(),
+	);
+}
+
+#[test]
+fn offenders_are_removed_at_the_next_session() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		report(&[3]);
+		System::assert_last_event(Event::ValidatorSet(ValidatorSetEvent::OffenderRemoved {
+			validator: 3,
+		}));
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2]);
+		// The offender is disabled right away.
+		assert_eq!(Session::disabled_validators(), vec![2]);
+
+		start_session(2);
+		assert_eq!(Session::validators(), vec![1, 2]);
+	});
+}
+
+#[test]
+fn offenders_are_not_removed_below_min_validators() {
+	new_test_ext().execute_with(|| {
+		// Only the first offender can be disabled and removed without going over
+		// `DisabledValidatorsThreshold` or below `MinValidators`, and account 4 is not a
+		// validator at all.
+		report(&[1, 2, 4]);
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![2, 3]);
+		assert_eq!(Session::disabled_validators(), vec![0]);
+	});
+}
+
+#[test]
+fn historical_session_manager_identifies_validators_by_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 3));
+		assert_eq!(
+			<ValidatorSet as pallet_session::historical::SessionManager<u64, u64>>::new_session(1),
+			Some(vec![(1, 1), (2, 2)])
+		);
+	});
+}
+
+#[test]
+fn disabled_validator_is_enabled_at_the_next_session() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			ValidatorSet::disable_validator(Origin::signed(1), 2),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(ValidatorSet::disable_validator(Origin::root(), 2));
+		System::assert_last_event(Event::ValidatorSet(ValidatorSetEvent::ValidatorDisabled {
+			validator: 2,
+		}));
+		assert!(<Session as DisabledValidators>::is_disabled(1));
+		// Disabling doesn't change the validator set.
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2, 3]);
+
+		start_session(1);
+		assert!(!<Session as DisabledValidators>::is_disabled(1));
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+	});
+}
+
+#[test]
+fn disable_validator_checks_the_active_set() {
+	new_test_ext().execute_with(|| {
+		// Account 4 is added to the set, but isn't active before the session after next.
+		register_keys(4);
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert_noop!(
+			ValidatorSet::disable_validator(Origin::root(), 4),
+			Error::<Test>::NotValidator
+		);
+
+		assert_ok!(ValidatorSet::disable_validator(Origin::root(), 1));
+		assert_noop!(
+			ValidatorSet::disable_validator(Origin::root(), 1),
+			Error::<Test>::AlreadyDisabled
+		);
+		// Half of the three active validators rounds down to one.
+		assert_noop!(
+			ValidatorSet::disable_validator(Origin::root(), 2),
+			Error::<Test>::TooManyDisabled
+		);
+	});
+}
+
+#[test]
+fn one_validator_can_be_disabled_in_a_small_set() {
+	new_test_ext().execute_with(|| {
+		// A third of the three active validators rounds down to zero.
+		DisabledValidatorsThreshold::set(Perbill::from_percent(33));
+
+		assert_ok!(ValidatorSet::disable_validator(Origin::root(), 1));
+		assert!(<Session as DisabledValidators>::is_disabled(0));
+		assert_noop!(
+			ValidatorSet::disable_validator(Origin::root(), 2),
+			Error::<Test>::TooManyDisabled
+		);
+	});
+}
+
+#[test]
+fn historical_sessions_are_pruned_after_the_historical_depth() {
+	new_test_ext().execute_with(|| {
+		// Genesis notes the first session and the one queued after it.
+		assert!(Historical::historical_root(0).is_some());
+		assert!(Historical::historical_root(1).is_some());
+
+		start_session(2);
+		assert!(Historical::historical_root(0).is_some());
+
+		start_session(4);
+		assert_eq!(Historical::historical_root(0), None);
+		assert_eq!(Historical::historical_root(1), None);
+		for index in 2..=5 {
+			assert!(Historical::historical_root(index).is_some());
+		}
+	});
+}
@@ -0,0 +1,72 @@
+//! Weights for pallet_validator_set
+//!
+//! These weights are estimated by hand from the storage accesses of each call and have not
+//! been generated by the benchmark CLI. Regenerate them on reference hardware with the
+//! command below, which overwrites this file, before relying on them in production.
+
+// Command to generate the weights:
+// ./target/release/node-template
+// benchmark
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet=pallet_validator_set
+// --extrinsic=*
+// --steps=50
+// --repeat=20
+// --output=./pallets/validator-set/src/weights.rs
+// --template=./.maintain/frame-weight-template.hbs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_validator_set.
+pub trait WeightInfo {
+	fn add_validator() -> Weight;
+	fn remove_validator() -> Weight;
+	fn disable_validator() -> Weight;
+}
+
+/// Hand-estimated weights for pallet_validator_set, see the module documentation.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn add_validator() -> Weight {
+		(28_416_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn remove_validator() -> Weight {
+		(27_953_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn disable_validator() -> Weight {
+		(24_871_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn add_validator() -> Weight {
+		(28_416_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn remove_validator() -> Weight {
+		(27_953_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn disable_validator() -> Weight {
+		(24_871_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
@@ -0,0 +1,14 @@
+//! Runtime API definition for the transaction fee multiplier.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_runtime::FixedU128;
+
+sp_api::decl_runtime_apis! {
+	/// The API to query the multiplier applied to the weight fee of transactions.
+	pub trait FeeMultiplierApi {
+		/// The multiplier that will be applied to the weight fee of transactions in the next
+		/// block.
+		fn next_fee_multiplier() -> FixedU128;
+	}
+}
@@ -0,0 +1,70 @@
+//! RPC interface for the transaction fee multiplier.
+
+use std::sync::Arc;
+
+pub use fee_multiplier_runtime_api::FeeMultiplierApi as FeeMultiplierRuntimeApi;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT, FixedPointNumber};
+
+/// Fee multiplier RPC methods.
+#[rpc]
+pub trait FeeMultiplierApi<BlockHash> {
+	/// Get the multiplier applied to the weight fee of transactions in the next block.
+	///
+	/// The multiplier is a fixed point number, returned as its inner value scaled by `10^18`.
+	#[rpc(name = "fee_nextMultiplier")]
+	fn next_multiplier(&self, at: Option<BlockHash>) -> Result<NumberOrHex>;
+}
+
+/// A struct that implements the [`FeeMultiplierApi`].
+pub struct FeeMultiplier<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> FeeMultiplier<C, B> {
+	/// Create new `FeeMultiplier` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block> FeeMultiplierApi<<Block as BlockT>::Hash> for FeeMultiplier<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: FeeMultiplierRuntimeApi<Block>,
+{
+	fn next_multiplier(&self, at: Option<<Block as BlockT>::Hash>) -> Result<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.next_fee_multiplier(&at).map(|multiplier| multiplier.into_inner().into()).map_err(|e| {
+			RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to query the fee multiplier.".into(),
+				data: Some(e.to_string().into()),
+			}
+		})
+	}
+}
@@ -0,0 +1,67 @@
+//! Some configurable implementations as associated type for the node template runtime.
+
+use crate::{AccountId, Balances, Origin, Runtime, Sudo, System, Treasury, TreasuryFeeShare};
+use frame_support::traits::{Currency, EnsureOrigin, FindAuthor, Imbalance, OnUnbalanced};
+
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+/// The author of the current block, found the same way `pallet_authorship` finds it.
+///
+/// Unlike `Authorship::author`, this doesn't fall back to the default account when the block has
+/// no known author.
+pub fn block_author() -> Option<AccountId> {
+	let digest = System::digest();
+	let pre_runtime_digests = digest.logs().iter().filter_map(|d| d.as_pre_runtime());
+	<Runtime as pallet_authorship::Config>::FindAuthor::find_author(pre_runtime_digests)
+}
+
+/// Credit the author of the current block, or the treasury if there is no known author.
+pub struct ToAuthor;
+
+impl OnUnbalanced<NegativeImbalance> for ToAuthor {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		match block_author() {
+			Some(author) => Balances::resolve_creating(&author, amount),
+			None => Treasury::on_unbalanced(amount),
+		}
+	}
+}
+
+/// Split transaction fees between the treasury and the block author, and give tips to the author.
+///
+/// The treasury gets `TreasuryFeeShare` of the fees and the author gets the rest.
+pub struct DealWithFees;
+
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
+		if let Some(fees) = fees_then_tips.next() {
+			let to_treasury = TreasuryFeeShare::get() * fees.peek();
+			let (to_treasury, mut to_author) = fees.split(to_treasury);
+			if let Some(tips) = fees_then_tips.next() {
+				tips.merge_into(&mut to_author);
+			}
+			Treasury::on_unbalanced(to_treasury);
+			ToAuthor::on_unbalanced(to_author);
+		}
+	}
+}
+
+/// Ensure that the origin is signed by the current sudo key, e.g. a multisig of the governance
+/// accounts.
+pub struct EnsureSudoKey;
+
+impl EnsureOrigin<Origin> for EnsureSudoKey {
+	type Success = AccountId;
+
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		o.into().and_then(|o| match o {
+			frame_system::RawOrigin::Signed(who) if who == Sudo::key() => Ok(who),
+			o => Err(Origin::from(o)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::signed(Sudo::key())
+	}
+}
@@ -6,17 +6,31 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+/// Implementations of some helper traits passed into runtime modules as associated types.
+pub mod impls;
+use impls::{DealWithFees, EnsureSudoKey};
+/// Storage migrations run by `Executive` on runtime upgrades.
+pub mod migrations;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode, MaxEncodedLen};
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
+use pallet_session::historical as pallet_session_historical;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
-	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiSignature,
+	traits::{
+		self, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor,
+		OpaqueKeys, StaticLookup, Verify,
+	},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
+	ApplyExtrinsicResult, MultiSignature, RuntimeDebug, SaturatedConversion,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -26,23 +40,28 @@ use sp_version::RuntimeVersion;
 // A few exports that help ease life for downstream crates.
 pub use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{KeyOwnerProofSystem, Randomness, StorageInfo},
+	traits::{InstanceFilter, KeyOwnerProofSystem, Randomness, StorageInfo},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
-		IdentityFee, Weight,
+		IdentityFee, Weight, WeightToFeeCoefficient, WeightToFeeCoefficients,
+		WeightToFeePolynomial,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
-use pallet_transaction_payment::CurrencyAdapter;
+use pallet_transaction_payment::{CurrencyAdapter, Multiplier, TargetedFeeAdjustment};
+use smallvec::smallvec;
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{FixedPointNumber, Perbill, Permill, Perquintill};
 
 /// Import the template pallet.
 pub use pallet_template;
 
+/// Import the validator set pallet.
+pub use pallet_validator_set;
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -96,12 +115,12 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// The version of the runtime specification. A full node will not attempt to use its native
 	//   runtime in substitute for the on-chain Wasm runtime unless all of `spec_name`,
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
-	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
-	//   the compatible custom types.
-	spec_version: 100,
+	// This value started at 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
+	//   the compatible custom types, and is bumped with every runtime upgrade.
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -121,6 +140,16 @@ pub const MINUTES: BlockNumber = 60_000 / (MILLISECS_PER_BLOCK as BlockNumber);
 pub const HOURS: BlockNumber = MINUTES * 60;
 pub const DAYS: BlockNumber = HOURS * 24;
 
+// Currency units, with 12 decimals.
+pub const UNIT: Balance = 1_000_000_000_000;
+pub const MILLIUNIT: Balance = UNIT / 1_000;
+pub const MICROUNIT: Balance = MILLIUNIT / 1_000;
+
+/// The deposit for keeping `items` storage items of `bytes` bytes in total.
+pub const fn deposit(items: u32, bytes: u32) -> Balance {
+	items as Balance * 100 * MILLIUNIT + (bytes as Balance) * MILLIUNIT
+}
+
 /// The version information used to identify this runtime when compiled natively.
 #[cfg(feature = "std")]
 pub fn native_version() -> NativeVersion {
@@ -195,16 +224,72 @@ impl frame_system::Config for Runtime {
 
 impl pallet_randomness_collective_flip::Config for Runtime {}
 
+parameter_types! {
+	pub const MinValidators: u32 = 1;
+	pub const MaxValidators: u32 = 100;
+	/// Keep the validator sets of past sessions for as long as offences in them can be reported.
+	pub HistoricalDepth: u32 = (ReportLongevity::get() / SessionPeriod::get() as u64) as u32;
+}
+
+impl pallet_validator_set::Config for Runtime {
+	type Event = Event;
+	type AddRemoveOrigin = frame_system::EnsureRoot<AccountId>;
+	type MinValidators = MinValidators;
+	type MaxValidators = MaxValidators;
+	type HistoricalDepth = HistoricalDepth;
+	type WeightInfo = pallet_validator_set::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const SessionPeriod: BlockNumber = 10 * MINUTES;
+	pub const SessionOffset: BlockNumber = 0;
+	pub const DisabledValidatorsThreshold: Perbill = Perbill::from_percent(33);
+}
+
+impl pallet_session::Config for Runtime {
+	type Event = Event;
+	type ValidatorId = <Self as frame_system::Config>::AccountId;
+	type ValidatorIdOf = pallet_validator_set::ValidatorOf<Self>;
+	type ShouldEndSession = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+	type NextSessionRotation = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+	/// The validator set is managed by `ValidatorSet`. Every session is noted by `Historical`, so
+	/// that key ownership can be proven for past sessions.
+	type SessionManager = pallet_session_historical::NoteHistoricalRoot<Self, ValidatorSet>;
+	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = opaque::SessionKeys;
+	type DisabledValidatorsThreshold = DisabledValidatorsThreshold;
+	type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_session_historical::Config for Runtime {
+	type FullIdentification = AccountId;
+	type FullIdentificationOf = pallet_validator_set::ValidatorOf<Self>;
+}
+
+impl pallet_offences::Config for Runtime {
+	type Event = Event;
+	type IdentificationTuple = pallet_session_historical::IdentificationTuple<Self>;
+	/// Offenders are removed from the validator set.
+	type OnOffenceHandler = ValidatorSet;
+}
+
 impl pallet_aura::Config for Runtime {
 	type AuthorityId = AuraId;
-	type DisabledValidators = ();
+	/// Authorities disabled through `ValidatorSet` can't author blocks until the next session.
+	type DisabledValidators = Session;
+}
+
+parameter_types! {
+	/// Equivocation reports are kept in the pool for as long as the key ownership proofs they
+	/// carry are likely to be checkable.
+	pub ReportLongevity: u64 = 6 * SessionPeriod::get() as u64;
 }
 
 impl pallet_grandpa::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
 
-	type KeyOwnerProofSystem = ();
+	type KeyOwnerProofSystem = Historical;
 
 	type KeyOwnerProof =
 		<Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(KeyTypeId, GrandpaId)>>::Proof;
@@ -214,7 +299,11 @@ impl pallet_grandpa::Config for Runtime {
 		GrandpaId,
 	)>>::IdentificationTuple;
 
-	type HandleEquivocation = ();
+	type HandleEquivocation = pallet_grandpa::EquivocationHandler<
+		Self::KeyOwnerIdentification,
+		Offences,
+		ReportLongevity,
+	>;
 
 	type WeightInfo = ();
 }
@@ -231,34 +320,126 @@ impl pallet_timestamp::Config for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const UncleGenerations: BlockNumber = 0;
+}
+
+impl pallet_authorship::Config for Runtime {
+	/// Map the Aura authority of the block's slot to the validator account through the session.
+	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
+	/// Aura has no uncles.
+	type UncleGenerations = UncleGenerations;
+	type FilterUncle = ();
+	type EventHandler = BlockReward;
+}
+
 parameter_types! {
 	pub const ExistentialDeposit: u128 = 500;
 	pub const MaxLocks: u32 = 50;
+	pub const MaxReserves: u32 = 50;
 }
 
 impl pallet_balances::Config for Runtime {
 	type MaxLocks = MaxLocks;
-	type MaxReserves = ();
+	type MaxReserves = MaxReserves;
 	type ReserveIdentifier = [u8; 8];
 	/// The type for recording an account's balance.
 	type Balance = Balance;
 	/// The ubiquitous event type.
 	type Event = Event;
-	type DustRemoval = ();
+	/// Dust of reaped accounts goes to the treasury.
+	type DustRemoval = Treasury;
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
 }
 
+/// Converts the weight of a transaction into its fee.
+///
+/// The fee grows linearly with the weight, so that a transaction of `ExtrinsicBaseWeight` costs
+/// a tenth of a `MILLIUNIT` before the fee multiplier is applied.
+pub struct WeightToFee;
+
+impl WeightToFeePolynomial for WeightToFee {
+	type Balance = Balance;
+	fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+		let p = MILLIUNIT / 10;
+		let q = Balance::from(ExtrinsicBaseWeight::get());
+		smallvec![WeightToFeeCoefficient {
+			degree: 1,
+			negative: false,
+			coeff_frac: Perbill::from_rational(p % q, q),
+			coeff_integer: p / q,
+		}]
+	}
+}
+
 parameter_types! {
-	pub const TransactionByteFee: Balance = 1;
+	pub const TransactionByteFee: Balance = MICROUNIT;
+	/// The share of the normal dispatch class (`NORMAL_DISPATCH_RATIO` of `BlockWeights`) that
+	/// blocks should be filled to. Fuller blocks make fees go up, emptier ones make them go down.
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	/// How fast the multiplier reacts to blocks that miss `TargetBlockFullness`.
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(3, 100_000);
+	/// The multiplier never drops below this, so that fees can't become negligible: the weight
+	/// fee of a transaction is at least a tenth of what `WeightToFee` charges for it.
+	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 10u128);
 }
 
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	/// Fees and tips are split between the treasury and the block author.
+	type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees>;
 	type TransactionByteFee = TransactionByteFee;
-	type WeightToFee = IdentityFee<Balance>;
-	type FeeMultiplierUpdate = ();
+	type WeightToFee = WeightToFee;
+	/// Adjust fees to the congestion of the normal dispatch class after every block.
+	type FeeMultiplierUpdate =
+		TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+}
+
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: Balance = UNIT;
+	pub const SpendPeriod: BlockNumber = DAYS;
+	pub const Burn: Permill = Permill::from_percent(1);
+	pub const MaxApprovals: u32 = 100;
+	/// The share of transaction fees that goes to the treasury. The block author gets the rest,
+	/// as well as all tips.
+	pub const TreasuryFeeShare: Perbill = Perbill::from_percent(80);
+}
+
+impl pallet_treasury::Config for Runtime {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	type ApproveOrigin = frame_system::EnsureRoot<AccountId>;
+	type RejectOrigin = frame_system::EnsureRoot<AccountId>;
+	type Event = Event;
+	/// Bonds of rejected proposals go to the treasury.
+	type OnSlash = Treasury;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type SpendPeriod = SpendPeriod;
+	type Burn = Burn;
+	type BurnDestination = ();
+	type SpendFunds = ();
+	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+	type MaxApprovals = MaxApprovals;
+}
+
+parameter_types! {
+	pub const InflationPeriod: BlockNumber = 365 * DAYS;
+	pub const InitialRewardPerBlock: Balance = UNIT;
+	pub const InitialMaxInflation: Perbill = Perbill::from_percent(5);
+}
+
+impl pallet_block_reward::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type InflationPeriod = InflationPeriod;
+	type InitialRewardPerBlock = InitialRewardPerBlock;
+	type InitialMaxInflation = InitialMaxInflation;
+	type WeightInfo = pallet_block_reward::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -266,9 +447,292 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+impl pallet_utility::Config for Runtime {
+	type Event = Event;
+	/// Calls inside a batch are dispatched with the caller's origin, so `BaseCallFilter` and the
+	/// filters of a proxy still apply to each of them. A batch from root bypasses all filters,
+	/// like any other call from root.
+	type Call = Call;
+	/// `dispatch_as` can dispatch from any origin known to the runtime.
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = pallet_utility::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	/// One storage item: the multisig operation, keyed by its account and call hash.
+	pub const DepositBase: Balance = deposit(1, 88);
+	/// An additional 32 bytes for every approval.
+	pub const DepositFactor: Balance = deposit(0, 32);
+	pub const MaxSignatories: u16 = 100;
+}
+
+impl pallet_multisig::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type DepositBase = DepositBase;
+	type DepositFactor = DepositFactor;
+	type MaxSignatories = MaxSignatories;
+	type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
+}
+
+/// The calls a proxy may make on behalf of the account that added it.
+///
+/// Batches from `Utility` are allowed wherever they are useful, as each call in a batch is checked
+/// against the same filter.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, MaxEncodedLen)]
+pub enum ProxyType {
+	/// Any call, including adding and removing other proxies.
+	Any,
+	/// Any call that can't move funds out of the account: no `Balances` calls, no hand-over of
+	/// template entries and their deposits, and no `Sudo`, which can dispatch anything as root.
+	/// Calls that dispatch other calls later or from another account, outside of this filter,
+	/// are excluded too: `Scheduler`, `Preimages` and `Multisig`.
+	NonTransfer,
+	/// Only calls into the template pallets, e.g. for a hot key of an application.
+	TemplateOnly,
+	/// Only governance calls: `Sudo` and `Treasury`.
+	Governance,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::NonTransfer => !matches!(
+				c,
+				Call::Balances(..) |
+					Call::Sudo(..) |
+					Call::Scheduler(..) |
+					Call::Preimages(..) |
+					Call::Multisig(..) |
+					Call::TemplateModule(pallet_template::Call::transfer_entry(..)) |
+					Call::SecondTemplateModule(pallet_template::Call::transfer_entry(..))
+			),
+			ProxyType::TemplateOnly => matches!(
+				c,
+				Call::TemplateModule(..) | Call::SecondTemplateModule(..) | Call::Utility(..)
+			),
+			ProxyType::Governance =>
+				matches!(c, Call::Sudo(..) | Call::Treasury(..) | Call::Utility(..)),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		// `NonTransfer` excludes calls that `TemplateOnly` and `Governance` allow, so only `Any`
+		// covers the other types.
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			_ => false,
+		}
+	}
+}
+
+parameter_types! {
+	/// One storage item: the list of proxies of an account.
+	pub const ProxyDepositBase: Balance = deposit(1, 8);
+	/// An additional 33 bytes for every proxy: its account and type.
+	pub const ProxyDepositFactor: Balance = deposit(0, 33);
+	pub const MaxProxies: u16 = 32;
+	/// One storage item: the list of announcements of a proxy.
+	pub const AnnouncementDepositBase: Balance = deposit(1, 8);
+	/// An additional 68 bytes for every announcement: the real account, call hash and height.
+	pub const AnnouncementDepositFactor: Balance = deposit(0, 68);
+	pub const MaxPending: u16 = 32;
+}
+
+impl pallet_proxy::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+	type MaxPending = MaxPending;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+parameter_types! {
+	/// A scheduled call that doesn't fit in the rest of this weight is postponed to the next
+	/// block, unless it is the first of its block or has a priority of at most `HARD_DEADLINE`.
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * BlockWeights::get().max_block;
+	pub const MaxScheduledPerBlock: u32 = 50;
+}
+
+impl pallet_scheduler::Config for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	/// Governance acts through `Sudo` in this runtime, so calls can be scheduled by root, e.g.
+	/// through `Sudo::sudo`, or by the sudo key itself. Calls scheduled by the sudo key are
+	/// dispatched from its account, so a runtime upgrade scheduled that way is wrapped in
+	/// `Sudo::sudo`. Either way the sudo key doesn't have to be online at the scheduled block.
+	type ScheduleOrigin = frame_system::EnsureOneOf<
+		AccountId,
+		frame_system::EnsureRoot<AccountId>,
+		EnsureSudoKey,
+	>;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	/// Large enough for a runtime upgrade, while a preimage still fits in the normal share of a
+	/// block.
+	pub const MaxPreimageLen: u32 = 3 * 1024 * 1024;
+	/// One storage item: the preimage with its provider and deposit.
+	pub const PreimageBaseDeposit: Balance = deposit(1, 48);
+	pub const PreimageByteDeposit: Balance = deposit(0, 1);
+}
+
+impl pallet_preimages::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	/// Large calls such as `System::set_code` are noted by anyone, and then scheduled or
+	/// dispatched by hash through `Sudo`.
+	type DispatchOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxPreimageLen = MaxPreimageLen;
+	type PreimageBaseDeposit = PreimageBaseDeposit;
+	type PreimageByteDeposit = PreimageByteDeposit;
+	type WeightInfo = pallet_preimages::weights::SubstrateWeight<Runtime>;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		public: <Signature as traits::Verify>::Signer,
+		account: AccountId,
+		nonce: Index,
+	) -> Option<(Call, <UncheckedExtrinsic as traits::Extrinsic>::SignaturePayload)> {
+		let tip = 0;
+		// take the biggest period possible.
+		let period =
+			BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2) as u64;
+		let current_block = System::block_number()
+			.saturated_into::<u64>()
+			// The `System::block_number` is initialized with `n+1`,
+			// so the actual block number is `n`.
+			.saturating_sub(1);
+		let extra = (
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+			pallet_template::CheckTemplateRateLimit::<Runtime>::new(),
+			pallet_template::CheckTemplateRateLimit::<Runtime, pallet_template::Instance1>::new(),
+			pallet_template::CheckFreeQuota::<Runtime>::new(),
+			pallet_template::CheckFreeQuota::<Runtime, pallet_template::Instance1>::new(),
+		);
+		let raw_payload = SignedPayload::new(call, extra).ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let address = <Runtime as frame_system::Config>::Lookup::unlookup(account);
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (address, signature.into(), extra)))
+	}
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as traits::Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	Call: From<C>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = UncheckedExtrinsic;
+}
+
+parameter_types! {
+	pub const TemplateReserveId: [u8; 8] = *b"template";
+	pub const TemplateStorageDeposit: Balance = 1_000_000_000;
+	pub const TemplateUnsignedInterval: BlockNumber = 10;
+	pub const TemplateUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+	pub const TemplateProcessingPeriod: BlockNumber = 10 * MINUTES;
+	/// Keeps a full queue run well within the share of `BlockWeights` left to hooks.
+	pub const TemplateMaxQueueLen: u32 = 64;
+	pub const TemplateMaxPayloadLen: u32 = 4 * 1024;
+	pub const TemplatePayloadByteDeposit: Balance = 10_000_000;
+	pub const TemplateRateLimitWindow: BlockNumber = MINUTES;
+	pub const TemplateMaxCallsPerWindow: u32 = 5;
+	pub const TemplateFreeQuotaEra: BlockNumber = HOURS;
+	pub const TemplateFreeCallsPerEra: u32 = 10;
+	/// Enough for any call that takes an account, but not for a sizeable payload.
+	pub const TemplateMaxFreeCallLen: u32 = 64;
+	pub const TemplateMaxAuthorities: u32 = 32;
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
 	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ReserveId = TemplateReserveId;
+	type StorageDeposit = TemplateStorageDeposit;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type AuthorityId = pallet_template::crypto::TemplateAuthId;
+	type MaxAuthorities = TemplateMaxAuthorities;
+	type UnsignedInterval = TemplateUnsignedInterval;
+	type UnsignedPriority = TemplateUnsignedPriority;
+	type ProcessingPeriod = TemplateProcessingPeriod;
+	type MaxQueueLen = TemplateMaxQueueLen;
+	type MaxPayloadLen = TemplateMaxPayloadLen;
+	type PayloadByteDeposit = TemplatePayloadByteDeposit;
+	type RateLimitWindow = TemplateRateLimitWindow;
+	type MaxCallsPerWindow = TemplateMaxCallsPerWindow;
+	type FreeQuotaEra = TemplateFreeQuotaEra;
+	type FreeCallsPerEra = TemplateFreeCallsPerEra;
+	type MaxFreeCallLen = TemplateMaxFreeCallLen;
+	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const SecondTemplateReserveId: [u8; 8] = *b"tmplsec2";
+}
+
+/// Configure a second, independent instance of pallet-template.
+impl pallet_template::Config<pallet_template::Instance1> for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ReserveId = SecondTemplateReserveId;
+	type StorageDeposit = TemplateStorageDeposit;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type AuthorityId = pallet_template::second_crypto::SecondTemplateAuthId;
+	type MaxAuthorities = TemplateMaxAuthorities;
+	type UnsignedInterval = TemplateUnsignedInterval;
+	type UnsignedPriority = TemplateUnsignedPriority;
+	type ProcessingPeriod = TemplateProcessingPeriod;
+	type MaxQueueLen = TemplateMaxQueueLen;
+	type MaxPayloadLen = TemplateMaxPayloadLen;
+	type PayloadByteDeposit = TemplatePayloadByteDeposit;
+	type RateLimitWindow = TemplateRateLimitWindow;
+	type MaxCallsPerWindow = TemplateMaxCallsPerWindow;
+	type FreeQuotaEra = TemplateFreeQuotaEra;
+	type FreeCallsPerEra = TemplateFreeCallsPerEra;
+	type MaxFreeCallLen = TemplateMaxFreeCallLen;
+	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -281,13 +745,31 @@ construct_runtime!(
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Pallet, Storage},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		// `Authorship` must come before `Session` so that the author is found among the validators
+		// that produced the block, before a new session is started.
+		Authorship: pallet_authorship::{Pallet, Storage},
+		// `ValidatorSet` must come before `Session` so that its genesis set is available to it.
+		ValidatorSet: pallet_validator_set::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Session: pallet_session::{Pallet, Call, Storage, Config<T>, Event},
 		Aura: pallet_aura::{Pallet, Config<T>},
-		Grandpa: pallet_grandpa::{Pallet, Call, Storage, Config, Event},
+		Grandpa: pallet_grandpa::{Pallet, Call, Storage, Config, Event, ValidateUnsigned},
+		Historical: pallet_session_historical::{Pallet},
+		Offences: pallet_offences::{Pallet, Storage, Event},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
 		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Utility: pallet_utility::{Pallet, Call, Event},
+		Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>},
+		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>},
+		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>},
+		Preimages: pallet_preimages::{Pallet, Call, Storage, Event<T>},
+		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>},
+		BlockReward: pallet_block_reward::{Pallet, Call, Storage, Event<T>},
 		// Include the custom logic from the pallet-template in the runtime.
-		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		TemplateModule: pallet_template::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
+		SecondTemplateModule: pallet_template::<Instance1>::{
+			Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned
+		},
 	}
 );
 
@@ -306,9 +788,15 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_template::CheckTemplateRateLimit<Runtime>,
+	pallet_template::CheckTemplateRateLimit<Runtime, pallet_template::Instance1>,
+	pallet_template::CheckFreeQuota<Runtime>,
+	pallet_template::CheckFreeQuota<Runtime, pallet_template::Instance1>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
+/// The payload being signed in transactions.
+pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -316,6 +804,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPallets,
+	migrations::SeedValidatorSet,
 >;
 
 impl_runtime_apis! {
@@ -408,23 +897,24 @@ impl_runtime_apis! {
 		}
 
 		fn submit_report_equivocation_unsigned_extrinsic(
-			_equivocation_proof: fg_primitives::EquivocationProof<
+			equivocation_proof: fg_primitives::EquivocationProof<
 				<Block as BlockT>::Hash,
 				NumberFor<Block>,
 			>,
-			_key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
+			key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
 		) -> Option<()> {
-			None
+			let key_owner_proof = key_owner_proof.decode()?;
+
+			Grandpa::submit_unsigned_equivocation_report(equivocation_proof, key_owner_proof)
 		}
 
 		fn generate_key_ownership_proof(
 			_set_id: fg_primitives::SetId,
-			_authority_id: GrandpaId,
+			authority_id: GrandpaId,
 		) -> Option<fg_primitives::OpaqueKeyOwnershipProof> {
-			// NOTE: this is the only implementation possible since we've
-			// defined our key owner proof type as a bottom type (i.e. a type
-			// with no values).
-			None
+			Historical::prove((fg_primitives::KEY_TYPE, authority_id))
+				.map(|p| p.encode())
+				.map(fg_primitives::OpaqueKeyOwnershipProof::new)
 		}
 	}
 
@@ -449,6 +939,32 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl fee_multiplier_runtime_api::FeeMultiplierApi<Block> for Runtime {
+		fn next_fee_multiplier() -> Multiplier {
+			TransactionPayment::next_fee_multiplier()
+		}
+	}
+
+	impl pallet_template_runtime_api::TemplateApi<Block> for Runtime {
+		fn get_value() -> Option<u32> {
+			TemplateModule::something()
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	impl frame_try_runtime::TryRuntime<Block> for Runtime {
+		fn on_runtime_upgrade() -> (Weight, Weight) {
+			// Unwrap on purpose, so that a failing pre or post upgrade check stops right here
+			// with a backtrace.
+			let weight = Executive::try_runtime_upgrade().unwrap();
+			(weight, BlockWeights::get().max_block)
+		}
+
+		fn execute_block_no_check(block: Block) -> Weight {
+			Executive::execute_block_no_check(block)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -463,8 +979,16 @@ impl_runtime_apis! {
 
 			list_benchmark!(list, extra, frame_system, SystemBench::<Runtime>);
 			list_benchmark!(list, extra, pallet_balances, Balances);
+			list_benchmark!(list, extra, pallet_block_reward, BlockReward);
+			list_benchmark!(list, extra, pallet_multisig, Multisig);
+			list_benchmark!(list, extra, pallet_preimages, Preimages);
+			list_benchmark!(list, extra, pallet_proxy, Proxy);
+			list_benchmark!(list, extra, pallet_scheduler, Scheduler);
 			list_benchmark!(list, extra, pallet_timestamp, Timestamp);
+			list_benchmark!(list, extra, pallet_treasury, Treasury);
+			list_benchmark!(list, extra, pallet_utility, Utility);
 			list_benchmark!(list, extra, pallet_template, TemplateModule);
+			list_benchmark!(list, extra, pallet_validator_set, ValidatorSet);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -497,8 +1021,16 @@ impl_runtime_apis! {
 
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
+			add_benchmark!(params, batches, pallet_block_reward, BlockReward);
+			add_benchmark!(params, batches, pallet_multisig, Multisig);
+			add_benchmark!(params, batches, pallet_preimages, Preimages);
+			add_benchmark!(params, batches, pallet_proxy, Proxy);
+			add_benchmark!(params, batches, pallet_scheduler, Scheduler);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_treasury, Treasury);
+			add_benchmark!(params, batches, pallet_utility, Utility);
 			add_benchmark!(params, batches, pallet_template, TemplateModule);
+			add_benchmark!(params, batches, pallet_validator_set, ValidatorSet);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
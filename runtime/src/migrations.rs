@@ -0,0 +1,77 @@
+//! Storage migrations of the node template runtime.
+
+use crate::{
+	opaque::SessionKeys, AccountId, Aura, Grandpa, Origin, Runtime, Session, Signature,
+	ValidatorSet,
+};
+use frame_support::{
+	traits::{Get, OnRuntimeUpgrade},
+	weights::Weight,
+};
+use pallet_session::WeightInfo as _;
+use pallet_validator_set::WeightInfo as _;
+use sp_core::sr25519;
+use sp_runtime::traits::{IdentifyAccount, Verify};
+use sp_std::prelude::*;
+
+/// Seed `ValidatorSet` and the session keys of a chain that started before `Session` was added,
+/// from the Aura and GRANDPA authorities it was started with.
+///
+/// Without this, the set handed to `Session` stays empty and the chain can't rotate its
+/// validators. The account of each validator is derived from its Aura key, the way the chain
+/// spec derives both from the same seed, and the Aura and GRANDPA keys are paired in the order of
+/// the two authority lists. The keys are registered through `Session::set_keys`, so every
+/// validator account must exist on chain.
+///
+/// The seeded set is queued at the next session boundary and becomes active one session later.
+/// Until then Aura and GRANDPA keep their current authorities. Does nothing if `ValidatorSet`
+/// already holds validators.
+pub struct SeedValidatorSet;
+
+impl OnRuntimeUpgrade for SeedValidatorSet {
+	fn on_runtime_upgrade() -> Weight {
+		let db = <Runtime as frame_system::Config>::DbWeight::get();
+		if !ValidatorSet::validators().is_empty() {
+			return db.reads(1)
+		}
+
+		let aura = Aura::authorities();
+		let grandpa = Grandpa::grandpa_authorities();
+		let mut weight = db.reads(3);
+		if aura.len() != grandpa.len() {
+			log::error!(
+				target: "runtime::migrations",
+				"{} Aura and {} GRANDPA authorities can't be paired, not seeding the validator set",
+				aura.len(),
+				grandpa.len(),
+			);
+			return weight
+		}
+
+		for (aura, (grandpa, _)) in aura.into_iter().zip(grandpa) {
+			let public: sr25519::Public = aura.clone().into();
+			let who: AccountId = <Signature as Verify>::Signer::from(public).into_account();
+			let keys = SessionKeys { aura, grandpa };
+			let result = Session::set_keys(Origin::signed(who.clone()), keys, Vec::new())
+				.and_then(|_| ValidatorSet::add_validator(Origin::root(), who.clone()));
+			if let Err(e) = result {
+				log::error!(
+					target: "runtime::migrations",
+					"Failed to seed validator {:?}: {:?}",
+					who,
+					e,
+				);
+			}
+			let set_keys = <Runtime as pallet_session::Config>::WeightInfo::set_keys();
+			let add_validator =
+				<Runtime as pallet_validator_set::Config>::WeightInfo::add_validator();
+			weight = weight.saturating_add(set_keys).saturating_add(add_validator);
+		}
+		log::info!(
+			target: "runtime::migrations",
+			"Seeded {} validators from the Aura and GRANDPA authorities",
+			ValidatorSet::validators().len(),
+		);
+		weight
+	}
+}
@@ -0,0 +1,671 @@
+//! Integration tests of the runtime configuration.
+
+use crate::{impls::DealWithFees, *};
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::unhashed,
+	traits::{Currency, InstanceFilter, OnInitialize, OnRuntimeUpgrade, OnUnbalanced, OriginTrait},
+	weights::{DispatchClass, GetDispatchInfo, Pays, PostDispatchInfo},
+};
+use pallet_transaction_payment::{ChargeTransactionPayment, Multiplier};
+use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
+use sp_keyring::{Ed25519Keyring, Sr25519Keyring};
+use sp_runtime::{
+	traits::{Convert, Hash, SignedExtension},
+	transaction_validity::InvalidTransaction,
+	DispatchError,
+};
+
+const ENDOWMENT: Balance = 1 << 60;
+const TREASURY_SEED: Balance = 1_000 * UNIT;
+
+fn alice() -> AccountId {
+	Sr25519Keyring::Alice.to_account_id()
+}
+
+fn bob() -> AccountId {
+	Sr25519Keyring::Bob.to_account_id()
+}
+
+fn dave() -> AccountId {
+	Sr25519Keyring::Dave.to_account_id()
+}
+
+/// Build a chain with Alice as the only validator and the sudo key, endowed Alice and Bob, and a
+/// seeded treasury.
+fn new_test_ext() -> sp_io::TestExternalities {
+	let keys = opaque::SessionKeys {
+		aura: Sr25519Keyring::Alice.public().into(),
+		grandpa: Ed25519Keyring::Alice.public().into(),
+	};
+	let storage = GenesisConfig {
+		balances: BalancesConfig {
+			balances: vec![
+				(alice(), ENDOWMENT),
+				(bob(), ENDOWMENT),
+				(Treasury::account_id(), TREASURY_SEED),
+			],
+		},
+		validator_set: ValidatorSetConfig { initial_validators: vec![alice()] },
+		session: SessionConfig { keys: vec![(alice(), alice(), keys)] },
+		sudo: SudoConfig { key: alice() },
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Sign `call` as Bob and check it the way the transaction pool does.
+fn validate_as_bob(call: Call) -> TransactionValidity {
+	let extra: SignedExtra = (
+		frame_system::CheckSpecVersion::new(),
+		frame_system::CheckTxVersion::new(),
+		frame_system::CheckGenesis::new(),
+		frame_system::CheckEra::from(generic::Era::Immortal),
+		frame_system::CheckNonce::from(System::account_nonce(bob())),
+		frame_system::CheckWeight::new(),
+		ChargeTransactionPayment::from(0),
+		pallet_template::CheckTemplateRateLimit::new(),
+		pallet_template::CheckTemplateRateLimit::new(),
+		pallet_template::CheckFreeQuota::new(),
+		pallet_template::CheckFreeQuota::new(),
+	);
+	let payload = SignedPayload::new(call, extra).unwrap();
+	let signature = payload.using_encoded(|payload| Sr25519Keyring::Bob.sign(payload));
+	let (call, extra, _) = payload.deconstruct();
+	let xt = UncheckedExtrinsic::new_signed(call, bob().into(), signature.into(), extra);
+	Executive::validate_transaction(TransactionSource::External, xt, System::parent_hash())
+}
+
+/// Make Alice, the only validator, the author of the current block.
+fn author_block() {
+	System::deposit_log(generic::DigestItem::PreRuntime(AURA_ENGINE_ID, Slot::from(1).encode()));
+}
+
+#[test]
+fn fees_are_split_between_treasury_and_author_and_tips_go_to_author() {
+	new_test_ext().execute_with(|| {
+		author_block();
+		let pot = Treasury::pot();
+
+		let call = Call::System(frame_system::Call::remark(vec![0; 32]));
+		let info = call.get_dispatch_info();
+		let len = call.encoded_size();
+		let tip = UNIT;
+		let pre = ChargeTransactionPayment::<Runtime>::from(tip)
+			.pre_dispatch(&bob(), &call, &info, len)
+			.unwrap();
+		let post_info = PostDispatchInfo { actual_weight: None, pays_fee: Pays::Yes };
+		assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+			pre,
+			&info,
+			&post_info,
+			len,
+			&Ok(())
+		));
+
+		let paid = TransactionPayment::compute_actual_fee(len as u32, &info, &post_info, tip);
+		let to_treasury = TreasuryFeeShare::get() * (paid - tip);
+		assert_eq!(Balances::free_balance(bob()), ENDOWMENT - paid);
+		assert_eq!(Treasury::pot(), pot + to_treasury);
+		assert_eq!(Balances::free_balance(alice()), ENDOWMENT + paid - to_treasury);
+	});
+}
+
+#[test]
+fn block_author_is_rewarded() {
+	new_test_ext().execute_with(|| {
+		author_block();
+		let issuance = Balances::total_issuance();
+
+		Authorship::on_initialize(1);
+
+		assert_eq!(Authorship::author(), alice());
+		assert_eq!(Balances::free_balance(alice()), ENDOWMENT + InitialRewardPerBlock::get());
+		assert_eq!(Balances::total_issuance(), issuance + InitialRewardPerBlock::get());
+	});
+}
+
+#[test]
+fn fees_go_to_the_treasury_without_a_known_author() {
+	new_test_ext().execute_with(|| {
+		let pot = Treasury::pot();
+
+		let fee = Balances::issue(1_000);
+		let tip = Balances::issue(100);
+		DealWithFees::on_unbalanceds(vec![fee, tip].into_iter());
+
+		assert_eq!(Treasury::pot(), pot + 1_100);
+		assert_eq!(Balances::free_balance(alice()), ENDOWMENT);
+	});
+}
+
+#[test]
+fn weight_fee_is_calibrated_to_the_extrinsic_base_weight() {
+	let base = ExtrinsicBaseWeight::get();
+	assert_eq!(WeightToFee::calc(&base), MILLIUNIT / 10);
+	assert_eq!(WeightToFee::calc(&(10 * base)), MILLIUNIT);
+	// A second of compute, half of the weight of a block.
+	assert_eq!(WeightToFee::calc(&WEIGHT_PER_SECOND), 800 * MILLIUNIT);
+}
+
+#[test]
+fn transfer_fee_scales_with_the_multiplier() {
+	new_test_ext().execute_with(|| {
+		let call = transfer_to_dave(UNIT);
+		let info = call.get_dispatch_info();
+		let len = call.encoded_size() as u32;
+
+		let fee = TransactionPayment::compute_fee(len, &info, 0);
+		assert_eq!(
+			fee,
+			WeightToFee::calc(&ExtrinsicBaseWeight::get()) +
+				len as Balance * TransactionByteFee::get() +
+				WeightToFee::calc(&info.weight)
+		);
+		assert!(fee > MILLIUNIT / 10 && fee < MILLIUNIT);
+
+		// Only the weight fee is multiplied.
+		pallet_transaction_payment::NextFeeMultiplier::<Runtime>::put(
+			Multiplier::saturating_from_integer(2),
+		);
+		assert_eq!(
+			TransactionPayment::compute_fee(len, &info, 0),
+			fee + WeightToFee::calc(&info.weight)
+		);
+	});
+}
+
+/// The fee multiplier after a block that is `fullness` full of normal transactions.
+fn next_multiplier(previous: Multiplier, fullness: Perbill) -> Multiplier {
+	new_test_ext().execute_with(|| {
+		let max_normal = BlockWeights::get().get(DispatchClass::Normal).max_total.unwrap();
+		System::register_extra_weight_unchecked(fullness * max_normal, DispatchClass::Normal);
+		<Runtime as pallet_transaction_payment::Config>::FeeMultiplierUpdate::convert(previous)
+	})
+}
+
+#[test]
+fn fee_multiplier_follows_block_fullness() {
+	let one = Multiplier::saturating_from_integer(1);
+	let full = next_multiplier(one, Perbill::one());
+	let empty = next_multiplier(one, Perbill::zero());
+
+	assert!(full > one);
+	assert!(empty < one);
+	// Neither moves the multiplier by more than `AdjustmentVariable` in one block.
+	assert!(full < one + AdjustmentVariable::get());
+	assert!(empty > one - AdjustmentVariable::get());
+}
+
+#[test]
+fn fee_multiplier_does_not_drop_below_the_minimum() {
+	let min = MinimumMultiplier::get();
+	assert_eq!(next_multiplier(min, Perbill::zero()), min);
+	assert!(next_multiplier(min, Perbill::one()) > min);
+}
+
+#[test]
+fn dust_goes_to_the_treasury() {
+	new_test_ext().execute_with(|| {
+		let pot = Treasury::pot();
+
+		assert_ok!(Balances::transfer(Origin::signed(alice()), dave().into(), 1_000));
+		// Dave is left with less than the existential deposit and gets reaped.
+		assert_ok!(Balances::transfer(Origin::signed(dave()), alice().into(), 600));
+
+		assert_eq!(Balances::total_balance(&dave()), 0);
+		assert_eq!(Treasury::pot(), pot + 400);
+	});
+}
+
+#[test]
+fn treasury_pays_out_approved_proposals() {
+	new_test_ext().execute_with(|| {
+		let value = 100 * UNIT;
+		let bond = ProposalBond::get() * value;
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(bob()), value, dave().into()));
+		assert_eq!(Balances::reserved_balance(bob()), bond);
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		let pot = Treasury::pot();
+		Treasury::on_initialize(SpendPeriod::get());
+
+		assert_eq!(Balances::free_balance(dave()), value);
+		// The bond is returned once the proposal is paid out.
+		assert_eq!(Balances::reserved_balance(bob()), 0);
+		assert_eq!(Balances::free_balance(bob()), ENDOWMENT);
+		// `Burn` of what is left in the pot is burned.
+		let left = pot - value;
+		assert_eq!(Treasury::pot(), left - Burn::get() * left);
+	});
+}
+
+#[test]
+fn rejected_proposals_forfeit_their_bond_to_the_treasury() {
+	new_test_ext().execute_with(|| {
+		let value = 100 * UNIT;
+		let bond = ProposalBond::get() * value;
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(bob()), value, dave().into()));
+		let pot = Treasury::pot();
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
+
+		assert_eq!(Treasury::pot(), pot + bond);
+		assert_eq!(Balances::free_balance(bob()), ENDOWMENT - bond);
+		assert_eq!(Balances::reserved_balance(bob()), 0);
+	});
+}
+
+fn transfer_to_dave(value: Balance) -> Call {
+	Call::Balances(pallet_balances::Call::transfer(dave().into(), value))
+}
+
+#[test]
+fn batch_dispatches_calls_in_order() {
+	new_test_ext().execute_with(|| {
+		let calls = vec![
+			transfer_to_dave(UNIT),
+			Call::TemplateModule(pallet_template::Call::do_something(42)),
+			transfer_to_dave(UNIT),
+		];
+		assert_ok!(Utility::batch(Origin::signed(bob()), calls));
+
+		assert_eq!(Balances::free_balance(dave()), 2 * UNIT);
+		assert_eq!(TemplateModule::something(), Some(42));
+		System::assert_last_event(Event::Utility(pallet_utility::Event::BatchCompleted));
+	});
+}
+
+#[test]
+fn batched_template_calls_count_against_the_rate_limit() {
+	new_test_ext().execute_with(|| {
+		let limit = TemplateMaxCallsPerWindow::get();
+		let calls = (0..=limit)
+			.map(|value| Call::SecondTemplateModule(pallet_template::Call::do_something(value)))
+			.collect::<Vec<_>>();
+		assert_ok!(Utility::batch(Origin::signed(bob()), calls));
+
+		System::assert_last_event(Event::Utility(pallet_utility::Event::BatchInterrupted(
+			limit,
+			pallet_template::Error::<Runtime, pallet_template::Instance1>::RateLimited.into(),
+		)));
+		assert_eq!(SecondTemplateModule::something(), Some(limit - 1));
+	});
+}
+
+#[test]
+fn batch_all_reverts_every_call_if_one_fails() {
+	new_test_ext().execute_with(|| {
+		let calls = vec![transfer_to_dave(UNIT), transfer_to_dave(2 * ENDOWMENT)];
+		assert!(Utility::batch_all(Origin::signed(bob()), calls).is_err());
+
+		assert_eq!(Balances::free_balance(dave()), 0);
+		assert_eq!(Balances::free_balance(bob()), ENDOWMENT);
+	});
+}
+
+#[test]
+fn call_filters_apply_to_batched_calls_except_from_root() {
+	new_test_ext().execute_with(|| {
+		// Filters added to an origin, e.g. by a proxy, are checked for every call in a batch.
+		let mut origin = Origin::signed(bob());
+		origin.add_filter(|call| !matches!(call, Call::Balances(_)));
+		let calls = vec![
+			Call::TemplateModule(pallet_template::Call::do_something(42)),
+			transfer_to_dave(UNIT),
+		];
+		assert_ok!(Utility::batch(origin, calls));
+
+		assert_eq!(TemplateModule::something(), Some(42));
+		assert_eq!(Balances::free_balance(dave()), 0);
+		assert!(matches!(
+			System::events().last().unwrap().event,
+			Event::Utility(pallet_utility::Event::BatchInterrupted(1, _))
+		));
+
+		// Root bypasses all filters, also inside a batch.
+		let mut root = Origin::root();
+		root.add_filter(|call| !matches!(call, Call::Balances(_)));
+		let force_transfer =
+			pallet_balances::Call::force_transfer(bob().into(), dave().into(), UNIT);
+		assert_ok!(Utility::batch(root, vec![Call::Balances(force_transfer)]));
+
+		assert_eq!(Balances::free_balance(dave()), UNIT);
+		assert!(matches!(
+			System::events().last().unwrap().event,
+			Event::Utility(pallet_utility::Event::BatchCompleted)
+		));
+	});
+}
+
+#[test]
+fn only_root_can_dispatch_as_another_origin() {
+	new_test_ext().execute_with(|| {
+		let as_bob = || Box::new(OriginCaller::system(frame_system::RawOrigin::Signed(bob())));
+		let call = Box::new(transfer_to_dave(UNIT));
+
+		assert_noop!(
+			Utility::dispatch_as(Origin::signed(alice()), as_bob(), call.clone()),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(Utility::dispatch_as(Origin::root(), as_bob(), call));
+		assert_eq!(Balances::free_balance(dave()), UNIT);
+		assert_eq!(Balances::free_balance(bob()), ENDOWMENT - UNIT);
+	});
+}
+
+#[test]
+fn multisig_sudo_key_needs_threshold_approvals() {
+	new_test_ext().execute_with(|| {
+		let mut signatories = vec![alice(), bob(), dave()];
+		signatories.sort();
+		let others = |who: AccountId| -> Vec<AccountId> {
+			signatories.iter().filter(|s| **s != who).cloned().collect()
+		};
+		let multisig = Multisig::multi_account_id(&signatories, 2);
+		assert_ok!(Sudo::set_key(Origin::signed(alice()), multisig.into()));
+
+		let call = Call::Sudo(pallet_sudo::Call::sudo(Box::new(Call::Balances(
+			pallet_balances::Call::set_balance(dave().into(), UNIT, 0),
+		))));
+		let max_weight = call.get_dispatch_info().weight;
+
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(alice()),
+			2,
+			others(alice()),
+			None,
+			call.encode(),
+			false,
+			0
+		));
+		let deposit = DepositBase::get() + 2 * DepositFactor::get();
+		assert_eq!(Balances::reserved_balance(alice()), deposit);
+		assert_eq!(Balances::free_balance(dave()), 0);
+
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(bob()),
+			2,
+			others(bob()),
+			Some(Multisig::timepoint()),
+			call.encode(),
+			false,
+			max_weight
+		));
+		assert_eq!(Balances::free_balance(dave()), UNIT);
+		assert_eq!(Balances::reserved_balance(alice()), 0);
+	});
+}
+
+#[test]
+fn proxy_types_filter_calls() {
+	let do_something = Call::TemplateModule(pallet_template::Call::do_something(42));
+	let transfer_entry =
+		Call::SecondTemplateModule(pallet_template::Call::transfer_entry(dave().into()));
+	let sudo = Call::Sudo(pallet_sudo::Call::sudo(Box::new(do_something.clone())));
+	let propose = Call::Treasury(pallet_treasury::Call::propose_spend(UNIT, dave().into()));
+	let batch = Call::Utility(pallet_utility::Call::batch(vec![]));
+	let calls = [&transfer_to_dave(UNIT), &do_something, &transfer_entry, &sudo, &propose, &batch];
+
+	let allowed = |proxy_type: ProxyType| -> Vec<bool> {
+		calls.iter().map(|call| proxy_type.filter(call)).collect()
+	};
+	assert_eq!(allowed(ProxyType::Any), vec![true, true, true, true, true, true]);
+	assert_eq!(allowed(ProxyType::NonTransfer), vec![false, true, false, false, true, true]);
+	assert_eq!(allowed(ProxyType::TemplateOnly), vec![false, true, true, false, false, true]);
+	assert_eq!(allowed(ProxyType::Governance), vec![false, false, false, true, true, true]);
+
+	assert!(ProxyType::Any.is_superset(&ProxyType::Governance));
+	assert!(!ProxyType::NonTransfer.is_superset(&ProxyType::TemplateOnly));
+	assert!(!ProxyType::TemplateOnly.is_superset(&ProxyType::Any));
+}
+
+#[test]
+fn template_only_proxy_cannot_transfer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_proxy(Origin::signed(alice()), bob(), ProxyType::TemplateOnly, 0));
+
+		let do_something = Call::TemplateModule(pallet_template::Call::do_something(42));
+		assert_ok!(Proxy::proxy(Origin::signed(bob()), alice(), None, Box::new(do_something)));
+		assert_eq!(TemplateModule::something(), Some(42));
+
+		// Batching doesn't get around the filter either.
+		let batch = Call::Utility(pallet_utility::Call::batch(vec![transfer_to_dave(UNIT)]));
+		assert_ok!(Proxy::proxy(Origin::signed(bob()), alice(), None, Box::new(batch)));
+		assert_ok!(Proxy::proxy(
+			Origin::signed(bob()),
+			alice(),
+			None,
+			Box::new(transfer_to_dave(UNIT))
+		));
+		assert!(matches!(
+			System::events().last().unwrap().event,
+			Event::Proxy(pallet_proxy::Event::ProxyExecuted(Err(_)))
+		));
+		assert_eq!(Balances::free_balance(dave()), 0);
+	});
+}
+
+#[test]
+fn free_call_does_not_get_around_proxy_filters() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_entry(Origin::signed(alice()), 42));
+		assert_ok!(Proxy::add_proxy(Origin::signed(alice()), bob(), ProxyType::NonTransfer, 0));
+
+		let transfer_entry = Box::new(pallet_template::Call::transfer_entry(bob().into()));
+		let call = Call::TemplateModule(pallet_template::Call::free_call(transfer_entry));
+		assert_ok!(Proxy::proxy(Origin::signed(bob()), alice(), None, Box::new(call)));
+
+		// The free call itself is allowed, but the entry stays with Alice.
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			Event::TemplateModule(pallet_template::Event::FreeCallExecuted {
+				result: Err(_),
+				..
+			})
+		)));
+		assert_eq!(TemplateModule::entry(alice()), Some(42));
+		assert_eq!(TemplateModule::entry(bob()), None);
+	});
+}
+
+#[test]
+fn pool_rejects_free_calls_over_the_quota_of_either_instance() {
+	new_test_ext().execute_with(|| {
+		let inner = Box::new(pallet_template::Call::do_something(42));
+		let call = Call::SecondTemplateModule(pallet_template::Call::free_call(inner.clone()));
+		// Only owners of an entry can make free calls.
+		let not_allowed =
+			InvalidTransaction::Custom(pallet_template::extensions::FREE_CALL_NOT_ALLOWED);
+		assert_eq!(validate_as_bob(call.clone()), Err(not_allowed.into()));
+		assert_ok!(SecondTemplateModule::set_entry(Origin::signed(bob()), 42));
+		assert_ok!(validate_as_bob(call.clone()));
+
+		for _ in 0..TemplateFreeCallsPerEra::get() {
+			assert_ok!(SecondTemplateModule::free_call(Origin::signed(bob()), inner.clone()));
+		}
+		// Move on to the next rate limit window, which is still in the same free quota era.
+		System::set_block_number(1 + TemplateRateLimitWindow::get());
+		let exhausted =
+			InvalidTransaction::Custom(pallet_template::extensions::FREE_QUOTA_EXHAUSTED);
+		assert_eq!(validate_as_bob(call), Err(exhausted.into()));
+	});
+}
+
+#[test]
+fn time_locked_proxy_must_announce_calls() {
+	new_test_ext().execute_with(|| {
+		let delay = 10;
+		assert_ok!(Proxy::add_proxy(Origin::signed(alice()), bob(), ProxyType::Any, delay));
+
+		let call = transfer_to_dave(UNIT);
+		assert_noop!(
+			Proxy::proxy(Origin::signed(bob()), alice(), None, Box::new(call.clone())),
+			pallet_proxy::Error::<Runtime>::Unannounced
+		);
+
+		assert_ok!(Proxy::announce(Origin::signed(bob()), alice(), BlakeTwo256::hash_of(&call)));
+		let deposit = AnnouncementDepositBase::get() + AnnouncementDepositFactor::get();
+		assert_eq!(Balances::reserved_balance(bob()), deposit);
+
+		System::set_block_number(delay);
+		assert_noop!(
+			Proxy::proxy_announced(
+				Origin::signed(dave()),
+				bob(),
+				alice(),
+				None,
+				Box::new(call.clone())
+			),
+			pallet_proxy::Error::<Runtime>::Unannounced
+		);
+
+		System::set_block_number(1 + delay);
+		assert_ok!(Proxy::proxy_announced(
+			Origin::signed(dave()),
+			bob(),
+			alice(),
+			None,
+			Box::new(call)
+		));
+		assert_eq!(Balances::free_balance(dave()), UNIT);
+		assert_eq!(Balances::reserved_balance(bob()), 0);
+	});
+}
+
+#[test]
+fn sudo_can_schedule_a_noted_call() {
+	new_test_ext().execute_with(|| {
+		let items = vec![(b"key".to_vec(), b"value".to_vec())];
+		let call = Call::System(frame_system::Call::set_storage(items));
+		let info = call.get_dispatch_info();
+		let bytes = call.encode();
+		let hash = BlakeTwo256::hash(&bytes);
+		assert_ok!(Preimages::note_preimage(Origin::signed(bob()), bytes));
+
+		let dispatch =
+			Call::Preimages(pallet_preimages::Call::dispatch_preimage(hash, info.weight));
+		let schedule =
+			Call::Scheduler(pallet_scheduler::Call::schedule(10, None, 0, Box::new(dispatch)));
+		assert_ok!(Sudo::sudo(Origin::signed(alice()), Box::new(schedule)));
+
+		Scheduler::on_initialize(9);
+		assert_eq!(sp_io::storage::get(b"key"), None);
+
+		Scheduler::on_initialize(10);
+		assert_eq!(sp_io::storage::get(b"key"), Some(b"value".to_vec()));
+		assert!(Preimages::preimage(hash).is_none());
+		assert_eq!(Balances::reserved_balance(bob()), 0);
+	});
+}
+
+/// Reads the same runtime version from any code, in place of a Wasm executor.
+struct ReadVersion(sp_version::RuntimeVersion);
+
+impl sp_core::traits::ReadRuntimeVersion for ReadVersion {
+	fn read_runtime_version(
+		&self,
+		_code: &[u8],
+		_ext: &mut dyn sp_externalities::Externalities,
+	) -> Result<Vec<u8>, String> {
+		Ok(self.0.encode())
+	}
+}
+
+#[test]
+fn sudo_can_dispatch_set_code_by_hash() {
+	let mut ext = new_test_ext();
+	let upgrade =
+		sp_version::RuntimeVersion { spec_version: VERSION.spec_version + 1, ..VERSION };
+	ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(ReadVersion(upgrade)));
+	ext.execute_with(|| {
+		let code = vec![1, 2, 3];
+		let call = Call::System(frame_system::Call::set_code(code.clone()));
+		let info = call.get_dispatch_info();
+		let bytes = call.encode();
+		let hash = BlakeTwo256::hash(&bytes);
+		assert_ok!(Preimages::note_preimage(Origin::signed(bob()), bytes));
+
+		let dispatch =
+			Call::Preimages(pallet_preimages::Call::dispatch_preimage(hash, info.weight));
+		// The upgrade can use the block space reserved for operational calls.
+		assert_eq!(dispatch.get_dispatch_info().class, DispatchClass::Operational);
+		assert_ok!(Sudo::sudo(Origin::signed(alice()), Box::new(dispatch)));
+
+		assert_eq!(sp_io::storage::get(sp_core::storage::well_known_keys::CODE), Some(code));
+		assert!(Preimages::preimage(hash).is_none());
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == Event::System(frame_system::Event::CodeUpdated)));
+	});
+}
+
+#[test]
+fn non_transfer_proxy_of_the_sudo_key_cannot_schedule_a_transfer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_proxy(Origin::signed(alice()), bob(), ProxyType::NonTransfer, 0));
+
+		let call = Box::new(transfer_to_dave(UNIT));
+		let schedule = Call::Scheduler(pallet_scheduler::Call::schedule(10, None, 0, call));
+		assert_ok!(Proxy::proxy(Origin::signed(bob()), alice(), None, Box::new(schedule)));
+		assert!(matches!(
+			System::events().last().unwrap().event,
+			Event::Proxy(pallet_proxy::Event::ProxyExecuted(Err(_)))
+		));
+
+		Scheduler::on_initialize(10);
+		assert_eq!(Balances::free_balance(dave()), 0);
+	});
+}
+
+#[test]
+fn only_root_and_the_sudo_key_can_schedule() {
+	new_test_ext().execute_with(|| {
+		let call = Box::new(transfer_to_dave(UNIT));
+		assert_noop!(
+			Scheduler::schedule(Origin::signed(bob()), 10, None, 0, call.clone()),
+			DispatchError::BadOrigin
+		);
+
+		// Calls scheduled by the sudo key are dispatched from its account.
+		assert_ok!(Scheduler::schedule(Origin::signed(alice()), 10, None, 0, call));
+		Scheduler::on_initialize(10);
+		assert_eq!(Balances::free_balance(dave()), UNIT);
+	});
+}
+
+#[test]
+fn validator_set_is_seeded_from_the_authorities_of_an_older_chain() {
+	new_test_ext().execute_with(|| {
+		// A chain started without `Session` only has Aura and GRANDPA authorities.
+		for prefix in [&b"Session"[..], b"ValidatorSet"] {
+			unhashed::kill_prefix(&sp_io::hashing::twox_128(prefix), None);
+		}
+		assert!(ValidatorSet::validators().is_empty());
+		let aura = Aura::authorities();
+		assert_eq!(aura, vec![Sr25519Keyring::Alice.public().into()]);
+
+		migrations::SeedValidatorSet::on_runtime_upgrade();
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![alice()]);
+
+		// The seeded set takes over without changing the authorities.
+		Session::rotate_session();
+		assert_eq!(Aura::authorities(), aura);
+		Session::rotate_session();
+		assert_eq!(Session::validators(), vec![alice()]);
+		assert_eq!(Aura::authorities(), aura);
+		let grandpa = Ed25519Keyring::Alice.public().into();
+		assert_eq!(Grandpa::grandpa_authorities(), vec![(grandpa, 1)]);
+
+		// Nothing happens on later upgrades.
+		migrations::SeedValidatorSet::on_runtime_upgrade();
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![alice()]);
+	});
+}